@@ -1,44 +1,105 @@
-use std::fmt;
+use thiserror::Error;
 
-#[derive(Debug)]
-pub struct ApiError {
-    pub details: String,
+/// Broad class of failure, for deciding what to tell the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// 404: the player or match doesn't exist on that shard.
+    NotFound,
+    /// 401: no key was sent.
+    Unauthorized,
+    /// 403: the key has expired, was revoked or never existed.
+    Forbidden,
+    /// 429 that outlasted every retry.
+    RateLimited,
+    /// 5xx: Riot is having a bad day.
+    ServerError,
+    /// Some other unexpected status code.
+    UnexpectedStatus,
+    /// The request never got a response.
+    Network,
+    /// A response arrived but didn't match our models.
+    Schema,
+    /// Reading or writing something on disk failed.
+    Storage,
+    /// Something the user typed doesn't make sense.
+    InvalidInput,
+    Other,
+}
+
+#[derive(Debug, Error)]
+pub enum ApiError {
+    #[error("{endpoint} returned HTTP {status}")]
+    Status { status: u16, endpoint: String },
+
+    #[error("request to {endpoint} failed")]
+    Network {
+        endpoint: String,
+        #[source]
+        source: reqwest::Error,
+    },
+
+    #[error("response from {endpoint} did not match the expected format")]
+    Schema {
+        endpoint: String,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// Stored data (a saved profile, an asset) that couldn't be parsed.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[error("{0}")]
+    InvalidInput(String),
+
+    #[error("{0}")]
+    Other(String),
 }
 
 impl ApiError {
     pub fn new(msg: &str) -> ApiError {
-        Self {
-            details: msg.to_string(),
-        }
+        Self::Other(msg.to_owned())
     }
-}
 
-impl fmt::Display for ApiError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.details)
+    pub fn invalid_input(msg: &str) -> ApiError {
+        Self::InvalidInput(msg.to_owned())
     }
-}
 
-impl From<std::io::Error> for ApiError {
-    fn from(e: std::io::Error) -> Self {
-        Self {
-            details: e.to_string(),
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            ApiError::Status { status, .. } => match status {
+                401 => ErrorKind::Unauthorized,
+                403 => ErrorKind::Forbidden,
+                404 => ErrorKind::NotFound,
+                429 => ErrorKind::RateLimited,
+                500..=599 => ErrorKind::ServerError,
+                _ => ErrorKind::UnexpectedStatus,
+            },
+            ApiError::Network { .. } => ErrorKind::Network,
+            ApiError::Schema { .. } => ErrorKind::Schema,
+            ApiError::Io(_) | ApiError::Json(_) => ErrorKind::Storage,
+            ApiError::InvalidInput(_) => ErrorKind::InvalidInput,
+            ApiError::Other(_) => ErrorKind::Other,
         }
     }
-}
 
-impl From<serde_json::Error> for ApiError {
-    fn from(e: serde_json::Error) -> Self {
-        Self {
-            details: e.to_string(),
-        }
+    /// Whether the same request might well succeed if made again later: the network dropped,
+    /// Riot had a server error, or the rate limit held out.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self.kind(),
+            ErrorKind::Network | ErrorKind::ServerError | ErrorKind::RateLimited
+        )
     }
-}
 
-impl From<reqwest::Error> for ApiError {
-    fn from(e: reqwest::Error) -> Self {
-        Self {
-            details: e.to_string(),
+    /// HTTP status code, if the failure was one.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            ApiError::Status { status, .. } => Some(*status),
+            _ => None,
         }
     }
 }
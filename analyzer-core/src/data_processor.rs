@@ -1,8 +1,17 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
 use serde_derive::{Deserialize, Serialize};
+
+use crate::api_error::ApiError;
+use crate::queue::QueueType;
 pub mod filter;
+pub mod inventory;
 pub mod items;
+pub mod lanes;
 
-use items::{Item, Items};
+use inventory::Inventory;
+use items::Items;
 
 #[derive(Default, Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct GraphData {
@@ -11,25 +20,197 @@ pub struct GraphData {
     pub game_end: i64,
     pub position: Position,
     pub champion: String,
-    pub gd15: i32,
+    /// Gold difference with the lane opponent at each of the profile's checkpoint minutes, item
+    /// value included. `None` where the game ended first.
+    #[serde(default)]
+    pub gd: BTreeMap<u32, Option<i32>>,
+    /// GD@15 from saves made before checkpoints were configurable; moved into `gd` on load.
+    #[serde(default, rename = "gd15", skip_serializing)]
+    legacy_gd15: Option<i32>,
+    /// Experience difference with the lane opponent at each checkpoint minute.
+    #[serde(default)]
+    pub xpd: BTreeMap<u32, Option<i32>>,
+    /// CS difference (lane minions and jungle camps) at each checkpoint minute.
+    #[serde(default)]
+    pub csd: BTreeMap<u32, Option<i32>>,
+    /// Level difference at each checkpoint minute.
+    #[serde(default)]
+    pub level_diff: BTreeMap<u32, Option<i32>>,
     pub csm: f32,
     pub dpm: f32,
     pub kp: f32,
     pub wl: bool,
+    #[serde(default)]
+    pub queue: QueueType,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
 pub struct RawData {
+    /// match-v5 id, e.g. ``NA1_5012345678``. Empty for games saved before it was recorded.
+    #[serde(default)]
+    pub match_id: String,
     pub pids: [(String, String); 5],
     pub me: Me,
-    pub g15: [(i32, i32); 5], // (blue, red) g@15
+    /// How the lanes stood at each checkpoint minute; see [`Checkpoint`].
+    #[serde(default)]
+    pub checkpoints: Vec<Checkpoint>,
+    /// Gold at 15 minutes from saves made before checkpoints were configurable.
+    #[serde(default, rename = "g15", skip_serializing)]
+    legacy_g15: Option<[(i32, i32); 5]>,
     pub csm: [(f32, f32); 5],
     pub dpm: [(f32, f32); 5],
     pub kp: [(f32, f32); 5],
     pub champs: [(String, String); 5],
     pub win_loss: (bool, bool),
     pub purchase_history: [(ItemHistory, ItemHistory); 5],
+    /// The player and their lane opponent minute by minute. Empty for games stored before it
+    /// was kept, and for games boiled down without a player in mind.
+    #[serde(default)]
+    pub series: LaneSeries,
     pub game_end: i64,
+    #[serde(default)]
+    pub queue: QueueType,
+}
+
+/// A minute of the game at which each player is compared with their lane opponent.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Checkpoint {
+    pub minute: u32,
+    /// `(blue, red)` gold on hand per lane, in [`Position::ALL`] order. `None` when the game
+    /// ended before `minute`, as are the other stats.
+    pub lanes: Option<[(i32, i32); 5]>,
+    /// `(blue, red)` experience per lane. Also `None` for games stored before it was kept.
+    #[serde(default)]
+    pub xp: Option<[(i32, i32); 5]>,
+    /// `(blue, red)` lane minions plus jungle camps per lane.
+    #[serde(default)]
+    pub cs: Option<[(i32, i32); 5]>,
+    #[serde(default)]
+    pub level: Option<[(i32, i32); 5]>,
+}
+
+impl Checkpoint {
+    /// Minutes a profile checks unless told otherwise.
+    pub const MINUTES: [u32; 3] = [10, 15, 20];
+
+    /// The checkpoint's time in game milliseconds.
+    pub fn timestamp(&self) -> i64 {
+        i64::from(self.minute) * 60_000
+    }
+
+    /// Reads a list of minutes such as ``10, 15, 20``, sorted and without repeats.
+    pub fn parse_minutes(input: &str) -> Result<Vec<u32>, ApiError> {
+        let mut minutes = input
+            .split([',', ' '])
+            .filter(|m| !m.is_empty())
+            .map(|m| match m.parse::<u32>() {
+                Ok(minute) if minute > 0 => Ok(minute),
+                _ => Err(ApiError::invalid_input(&format!(
+                    "{m} isn't a minute of the game"
+                ))),
+            })
+            .collect::<Result<Vec<u32>, ApiError>>()?;
+        if minutes.is_empty() {
+            return Err(ApiError::invalid_input(
+                "Pick at least one checkpoint minute",
+            ));
+        }
+        minutes.sort_unstable();
+        minutes.dedup();
+        Ok(minutes)
+    }
+}
+
+/// The player and their lane opponent at every frame of the game. Frames are a minute apart,
+/// except the last, which is when the game ended.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LaneSeries {
+    /// Game time of each frame, in milliseconds.
+    pub timestamps: Vec<i64>,
+    pub me: StatSeries,
+    pub opponent: StatSeries,
+}
+
+impl LaneSeries {
+    pub fn is_empty(&self) -> bool {
+        self.timestamps.is_empty()
+    }
+
+    /// How far into the game each frame was, in minutes.
+    pub fn minutes(&self) -> Vec<f64> {
+        self.timestamps
+            .iter()
+            .map(|t| *t as f64 / 60_000.0)
+            .collect()
+    }
+
+    /// The player's lead over their opponent in `stat` at each frame.
+    pub fn diff(&self, stat: SeriesStat) -> Vec<i32> {
+        self.me
+            .get(stat)
+            .iter()
+            .zip(self.opponent.get(stat))
+            .map(|(mine, theirs)| mine - theirs)
+            .collect()
+    }
+}
+
+/// One player's running totals, a value per frame.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StatSeries {
+    /// Gold earned so far, spent or not.
+    pub gold: Vec<i32>,
+    pub xp: Vec<i32>,
+    /// Lane minions plus jungle camps.
+    pub cs: Vec<i32>,
+    /// Damage dealt to champions.
+    pub damage: Vec<i32>,
+    pub level: Vec<i32>,
+}
+
+impl StatSeries {
+    pub fn get(&self, stat: SeriesStat) -> &[i32] {
+        match stat {
+            SeriesStat::Gold => &self.gold,
+            SeriesStat::Xp => &self.xp,
+            SeriesStat::Cs => &self.cs,
+            SeriesStat::Damage => &self.damage,
+            SeriesStat::Level => &self.level,
+        }
+    }
+}
+
+/// Which of a [`StatSeries`]'s values to look at.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SeriesStat {
+    #[default]
+    Gold,
+    Xp,
+    Cs,
+    Damage,
+    Level,
+}
+
+impl SeriesStat {
+    pub const ALL: [SeriesStat; 5] = [
+        SeriesStat::Gold,
+        SeriesStat::Xp,
+        SeriesStat::Cs,
+        SeriesStat::Damage,
+        SeriesStat::Level,
+    ];
+}
+
+impl fmt::Display for SeriesStat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeriesStat::Gold => write!(f, "Gold"),
+            SeriesStat::Xp => write!(f, "XP"),
+            SeriesStat::Cs => write!(f, "CS"),
+            SeriesStat::Damage => write!(f, "Damage to champions"),
+            SeriesStat::Level => write!(f, "Level"),
+        }
+    }
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -138,6 +319,30 @@ pub enum Position {
     SUPPORT,
 }
 
+impl Position {
+    /// Every lane, in the order the per-lane arrays in [`RawData`] use.
+    pub const ALL: [Position; 5] = [
+        Position::TOP,
+        Position::JUNGLE,
+        Position::MIDDLE,
+        Position::BOTTOM,
+        Position::SUPPORT,
+    ];
+
+    /// Reads match-v5's ``teamPosition``/``individualPosition``. Empty and ``Invalid`` (set for
+    /// games without lanes, like ARAM) are `None`.
+    pub fn from_riot(position: &str) -> Option<Position> {
+        match position {
+            "TOP" => Some(Position::TOP),
+            "JUNGLE" => Some(Position::JUNGLE),
+            "MIDDLE" => Some(Position::MIDDLE),
+            "BOTTOM" => Some(Position::BOTTOM),
+            "UTILITY" => Some(Position::SUPPORT),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Game {
     pub graph_data: GraphData,
@@ -151,6 +356,15 @@ impl Game {
             raw_data: raw,
         }
     }
+
+    /// Whether `raw` is this same game. Games are told apart by match id; older saves don't
+    /// have one, and for those a player can't have finished two games at the same millisecond.
+    pub fn is_same(&self, raw: &RawData) -> bool {
+        if !self.raw_data.match_id.is_empty() && !raw.match_id.is_empty() {
+            return self.raw_data.match_id == raw.match_id;
+        }
+        self.graph_data.game_end == raw.game_end
+    }
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
@@ -174,15 +388,55 @@ impl Games {
         self.games.is_empty()
     }
 
-    pub fn append_games(&mut self, raw_data: Vec<RawData>) {
+    /// Adds every game in `raw_data` that isn't stored yet, and says how many that was.
+    pub fn append_games(&mut self, raw_data: Vec<RawData>) -> usize {
+        let before = self.games.len();
         for data in raw_data {
-            self.games
-                .push(Game::new(Games::pull_graph_data(&data), data));
+            if !self.contains(&data) {
+                self.games
+                    .push(Game::new(Games::pull_graph_data(&data), data));
+            }
+        }
+        self.games.len() - before
+    }
+
+    /// Moves GD@15 from saves made before checkpoints were configurable into the 15 minute
+    /// checkpoint.
+    pub(crate) fn upgrade_legacy(&mut self) {
+        for game in &mut self.games {
+            if let Some(g15) = game.raw_data.legacy_g15.take()
+                && game.raw_data.checkpoints.is_empty()
+            {
+                game.raw_data.checkpoints.push(Checkpoint {
+                    minute: 15,
+                    lanes: Some(g15),
+                    ..Default::default()
+                });
+            }
+            if let Some(gd15) = game.graph_data.legacy_gd15.take()
+                && game.graph_data.gd.is_empty()
+            {
+                game.graph_data.gd.insert(15, Some(gd15));
+            }
+        }
+    }
+
+    pub fn contains(&self, raw: &RawData) -> bool {
+        self.games.iter().any(|g| g.is_same(raw))
+    }
+
+    /// Adds every game from `other` that isn't already here; see [`Game::is_same`].
+    pub fn merge(&mut self, other: Games) {
+        for game in other.games {
+            if !self.contains(&game.raw_data) {
+                self.games.push(game);
+            }
         }
+        self.sort_games();
     }
 
-    pub fn last_game_end(&self) -> i64 {
-        self.games.last().unwrap().graph_data.game_end
+    pub fn last_game_end(&self) -> Option<i64> {
+        self.games.last().map(|g| g.graph_data.game_end)
     }
 
     pub fn length(&self) -> usize {
@@ -206,6 +460,7 @@ impl Games {
             Side::BLUE => &data.pids[p_index].0,
             Side::RED => &data.pids[p_index].1,
         };
+        let items = Items::shared();
 
         GraphData {
             puuid: id.to_string(),
@@ -213,161 +468,63 @@ impl Games {
             game_end: data.game_end,
             position: data.me.pos.clone(),
             champion: data.me.champ.clone(),
-            gd15: Self::calc_gd(data, &side, &p_index),
+            gd: data
+                .checkpoints
+                .iter()
+                .map(|c| (c.minute, Self::calc_gd(data, &side, &p_index, c, &items)))
+                .collect(),
+            legacy_gd15: None,
+            xpd: Self::checkpoint_diffs(data, &side, &p_index, |c| c.xp),
+            csd: Self::checkpoint_diffs(data, &side, &p_index, |c| c.cs),
+            level_diff: Self::checkpoint_diffs(data, &side, &p_index, |c| c.level),
             csm: Self::find_csm(data, &side, &p_index),
             dpm: Self::find_dpm(data, &side, &p_index),
             kp: Self::find_kp(data, &side, &p_index),
             wl: Self::find_wl(data, &side),
+            queue: data.queue,
         }
         //todo!();
     }
 
-    fn calc_gd(game: &RawData, side: &Side, p_index: &usize) -> i32 {
+    fn calc_gd(
+        game: &RawData,
+        side: &Side,
+        p_index: &usize,
+        checkpoint: &Checkpoint,
+        items: &Items,
+    ) -> Option<i32> {
+        let (blue_gold, red_gold) = checkpoint.lanes?[*p_index];
+        let (blue_items, red_items) = &game.purchase_history[*p_index];
+        let until = checkpoint.timestamp();
+        let value = |history| Inventory::at(history, items, until).value(items) as i32;
+        let blue = blue_gold + value(blue_items);
+        let red = red_gold + value(red_items);
         match side {
-            Side::BLUE => {
-                (game.g15[*p_index].0
-                    + Self::inventory_item_value(&game.purchase_history[*p_index].0) as i32)
-                    - (game.g15[*p_index].1
-                        + Self::inventory_item_value(&game.purchase_history[*p_index].1) as i32)
-            }
-            Side::RED => {
-                (game.g15[*p_index].1
-                    + Self::inventory_item_value(&game.purchase_history[*p_index].0) as i32)
-                    - (game.g15[*p_index].0
-                        + Self::inventory_item_value(&game.purchase_history[*p_index].1) as i32)
-            }
+            Side::BLUE => Some(blue - red),
+            Side::RED => Some(red - blue),
         }
     }
 
-    fn inventory_item_value(history: &ItemHistory) -> i64 {
-        let items_list = Items::new();
-        let mut player_inv: Vec<Item> = Vec::new();
-        let mut out: i64 = 0;
-        let mut sorted_history: ItemHistory = history.clone();
-        sorted_history.sort();
-
-        for p in &sorted_history.events {
-            if !Self::check_valid_event(p, &items_list) {
-                continue;
-            }
-
-            println!("Action type: {:?}", p);
-
-            match p {
-                ItemEvent::PURCHASE {
-                    item_id,
-                    timestamp: _timestamp,
-                    pid: _pid,
-                } => player_inv.push(items_list.get_unchecked(item_id.to_string()).clone()),
-                ItemEvent::SELL {
-                    item_id,
-                    timestamp: _timestamp,
-                    pid: _pid,
-                } => {
-                    if let Some(index) = player_inv
-                        .iter()
-                        .position(|x| x.eq(items_list.get_unchecked(item_id.to_string())))
-                    {
-                        player_inv.remove(index);
+    /// The player's lead over their lane opponent in `stat` at each checkpoint.
+    fn checkpoint_diffs(
+        game: &RawData,
+        side: &Side,
+        p_index: &usize,
+        stat: impl Fn(&Checkpoint) -> Option<[(i32, i32); 5]>,
+    ) -> BTreeMap<u32, Option<i32>> {
+        game.checkpoints
+            .iter()
+            .map(|c| {
+                let diff = stat(c).map(|lanes| {
+                    let (blue, red) = lanes[*p_index];
+                    match side {
+                        Side::BLUE => blue - red,
+                        Side::RED => red - blue,
                     }
-                }
-                ItemEvent::DESTROY {
-                    item_id,
-                    timestamp: _timestamp,
-                    pid: _pid,
-                } => {
-                    if items_list.get_unchecked(item_id.to_string()).is_component()
-                        && let Some(index) = player_inv
-                            .iter()
-                            .position(|x| x.eq(items_list.get_unchecked(item_id.to_string())))
-                    {
-                        player_inv.remove(index);
-                    }
-                }
-                ItemEvent::UNDO {
-                    after_id,
-                    before_id,
-                    gold_gain,
-                    timestamp: _timestamp,
-                    pid: _pid,
-                } => {
-                    // if before was something and after is 0, we undid a buy
-                    // iff after is something and before was 0, we undid a sell
-                    if *after_id == 0 {
-                        if let Some(index) = player_inv
-                            .iter()
-                            .position(|x| x.eq(items_list.get_unchecked(before_id.to_string())))
-                        {
-                            player_inv.remove(index);
-                        }
-                    } else if *before_id == 0 {
-                        //sell
-                        player_inv.push(items_list.get_unchecked(after_id.to_string()).clone());
-                    }
-                    out += gold_gain; //gold offset for unaccounted for or lost components
-                }
-                ItemEvent::UNKNOWN => {}
-            };
-            //print!("Player inventory after event: ");
-            //for i in player_inv.clone() {
-            //    print!("{}", i.get_item_name());
-            //}
-            //println!();
-        }
-        let mut debug_string = Vec::<String>::new();
-
-        for i in player_inv {
-            debug_string.push(i.get_item_name());
-            out += i.get_item_value();
-        }
-
-        println!(
-            "Player inventory: {:?}\nTotal gold value of player items: {}",
-            debug_string, out
-        );
-
-        out
-    }
-
-    /* Checks if the event in the list is within the first 15 minutes*/
-    fn check_valid_event(event: &ItemEvent, items: &Items) -> bool {
-        match event {
-            ItemEvent::PURCHASE {
-                item_id,
-                timestamp,
-                pid: _pid,
-            }
-            | ItemEvent::SELL {
-                item_id,
-                timestamp,
-                pid: _pid,
-            }
-            | ItemEvent::DESTROY {
-                item_id,
-                timestamp,
-                pid: _pid,
-            } => {
-                if items.get(item_id.to_string()).is_some() {
-                    return timestamp <= &(900000);
-                }
-                false
-            }
-            ItemEvent::UNDO {
-                after_id,
-                before_id,
-                gold_gain: _gold_gain,
-                timestamp,
-                pid: _pid,
-            } => {
-                if *after_id == 0 && items.get(before_id.to_string()).is_some()
-                    || *before_id == 0 && items.get(after_id.to_string()).is_some()
-                {
-                    return timestamp <= &(900000);
-                }
-                false
-            }
-            ItemEvent::UNKNOWN => false,
-        }
+                });
+                (c.minute, diff)
+            })
+            .collect()
     }
 
     fn find_csm(game: &RawData, side: &Side, p_index: &usize) -> f32 {
@@ -1,219 +1,258 @@
-use std::collections::HashMap;
-
 use crate::{
-    data_processor::{ItemEvent, ItemHistory, Me, Position, RawData, Side},
+    data_processor::{
+        Checkpoint, Games, ItemEvent, ItemHistory, LaneSeries, Me, Position, RawData, Side,
+        StatSeries, lanes::Lanes,
+    },
     interface::{
-        match_data::MatchData,
-        timeline::{ParticipantFrames, Timeline},
+        match_data::{MatchData, Participant},
+        timeline::{Event, ParticipantFrame, ParticipantFrames, Timeline},
     },
+    queue::QueueType,
 };
 
 impl RawData {
+    /// Boils a game down with the default checkpoints, [`Checkpoint::MINUTES`].
     pub fn new(game_data: &MatchData, game_tl: &Timeline) -> RawData {
+        Self::with_checkpoints(game_data, game_tl, &Checkpoint::MINUTES)
+    }
+
+    /// Boils a game down, comparing lane opponents at each of `minutes`.
+    pub fn with_checkpoints(game_data: &MatchData, game_tl: &Timeline, minutes: &[u32]) -> RawData {
+        let lanes = Lanes::resolve(game_data, game_tl);
+        Self::build(game_data, game_tl, &lanes, minutes)
+    }
+
+    /// Boils a game down from the point of view of the player with `puuid`, keeping how they
+    /// and their lane opponent did minute by minute as well.
+    pub fn for_player(
+        game_data: &MatchData,
+        game_tl: &Timeline,
+        minutes: &[u32],
+        puuid: &String,
+    ) -> RawData {
+        let lanes = Lanes::resolve(game_data, game_tl);
+        let mut raw = Self::build(game_data, game_tl, &lanes, minutes);
+        raw.find_me(puuid);
+        raw.series = Self::filter_series(game_tl, &lanes, &raw.me);
+        raw
+    }
+
+    fn build(game_data: &MatchData, game_tl: &Timeline, lanes: &Lanes, minutes: &[u32]) -> RawData {
         RawData {
-            pids: Self::filter_pids(game_data),
+            pids: Self::filter_pids(game_data, lanes),
             me: Me::default(),
-            g15: Self::filter_g15(game_tl),
-            csm: Self::filter_csm(game_tl),
-            dpm: Self::filter_dpm(game_tl),
-            kp: Self::filter_kp(game_data),
-            champs: Self::filter_champs(game_data),
+            checkpoints: Self::filter_checkpoints(game_tl, lanes, minutes),
+            legacy_g15: None,
+            csm: Self::filter_csm(game_tl, lanes),
+            dpm: Self::filter_dpm(game_tl, lanes),
+            kp: Self::filter_kp(game_data, lanes),
+            champs: Self::filter_champs(game_data, lanes),
             win_loss: Self::find_wl(game_data),
-            purchase_history: Self::filter_purchases(game_tl),
+            purchase_history: Self::filter_purchases(game_tl, lanes),
+            series: LaneSeries::default(),
+            match_id: game_data.metadata.match_id.clone(),
             game_end: game_data.info.game_end_timestamp,
+            queue: QueueType::from_id(game_data.info.queue_id),
         }
     }
 
+    /// Whether the game reached at least the earliest of ``checkpoints``. Later checkpoints
+    /// the game fell short of are left empty by ``filter_checkpoints``.
+    pub fn can_analyze(game_tl: &Timeline, checkpoints: &[u32]) -> bool {
+        let earliest = checkpoints.iter().min().copied().unwrap_or(0) as usize;
+        // The per-minute averages read the second to last frame.
+        game_tl.info.frames.len() > earliest.max(2)
+    }
+
     pub fn find_me(&mut self, pid: &String) {
-        for i in 0..5 {
+        for (i, position) in Position::ALL.into_iter().enumerate() {
             if &self.pids[i].0 == pid {
                 self.me.side = Side::BLUE;
-                self.me.pos = match i {
-                    0 => Position::TOP,
-                    1 => Position::JUNGLE,
-                    2 => Position::MIDDLE,
-                    3 => Position::BOTTOM,
-                    4 => Position::SUPPORT,
-                    _ => Position::default(),
-                };
+                self.me.pos = position;
                 self.me.champ = self.champs[i].0.clone();
             } else if &self.pids[i].1 == pid {
                 self.me.side = Side::RED;
-                self.me.pos = match i {
-                    0 => Position::TOP,
-                    1 => Position::JUNGLE,
-                    2 => Position::MIDDLE,
-                    3 => Position::BOTTOM,
-                    4 => Position::SUPPORT,
-                    _ => Position::default(),
-                };
+                self.me.pos = position;
                 self.me.champ = self.champs[i].1.clone();
             }
         }
     }
 
-    /* Selects the item purchase history for target player and their lane opponent */
-    fn filter_purchases(game_tl: &Timeline) -> [(ItemHistory, ItemHistory); 5] {
-        let mut out: [(ItemHistory, ItemHistory); 5] = Default::default();
+    /// The champion the player's lane opponent played. Empty before [`RawData::find_me`].
+    pub fn opponent_champ(&self) -> &str {
+        let (blue, red) = &self.champs[Games::get_index_from_pos(&self.me.pos)];
+        match self.me.side {
+            Side::BLUE => red,
+            Side::RED => blue,
+        }
+    }
 
-        (0..5).for_each(|f| {
-            out[f] = (
-                Self::gen_player_purchases(game_tl, f + 1),
-                Self::gen_player_purchases(game_tl, f + 6),
-            );
-        });
-        out
+    /* Selects the item purchase history for target player and their lane opponent */
+    fn filter_purchases(game_tl: &Timeline, lanes: &Lanes) -> [(ItemHistory, ItemHistory); 5] {
+        std::array::from_fn(|i| {
+            let (blue, red) = lanes.ids(i);
+            (
+                Self::gen_player_purchases(game_tl, blue),
+                Self::gen_player_purchases(game_tl, red),
+            )
+        })
     }
 
     /* Generates a history of item-related events for a given player */
-    fn gen_player_purchases(game_tl: &Timeline, p_index: usize) -> ItemHistory {
+    fn gen_player_purchases(game_tl: &Timeline, pid: i64) -> ItemHistory {
         let mut out: ItemHistory = Default::default();
 
         for frame in &game_tl.info.frames {
             for event in &frame.events {
-                if let Some(pid) = event.participant_id
-                    && pid == (p_index as i64)
-                {
-                    let _: () = match event.type_field.as_str() {
-                        "ITEM_PURCHASED" => {
-                            out.events.push(ItemEvent::PURCHASE {
-                                item_id: event.item_id.unwrap(),
-                                timestamp: event.timestamp,
-                                pid: event.participant_id.unwrap(),
-                            });
-                        }
-                        "ITEM_DESTROYED" => {
-                            out.events.push(ItemEvent::DESTROY {
-                                item_id: event.item_id.unwrap(),
-                                timestamp: event.timestamp,
-                                pid: event.participant_id.unwrap(),
-                            });
-                        }
-                        "ITEM_SOLD" => {
-                            out.events.push(ItemEvent::SELL {
-                                item_id: event.item_id.unwrap(),
-                                timestamp: event.timestamp,
-                                pid: event.participant_id.unwrap(),
-                            });
-                        }
-                        "ITEM_UNDO" => {
-                            out.events.push(ItemEvent::UNDO {
-                                after_id: event.after_id.unwrap(),
-                                before_id: event.before_id.unwrap(),
-                                gold_gain: event.gold_gain.unwrap(),
-                                timestamp: event.timestamp,
-                                pid: event.participant_id.unwrap(),
-                            });
-                        }
-                        &_ => {}
-                    };
-                }
+                let item_event = match event {
+                    Event::ItemPurchased(e) if e.participant_id == pid => ItemEvent::PURCHASE {
+                        item_id: e.item_id,
+                        timestamp: e.timestamp,
+                        pid: e.participant_id,
+                    },
+                    Event::ItemDestroyed(e) if e.participant_id == pid => ItemEvent::DESTROY {
+                        item_id: e.item_id,
+                        timestamp: e.timestamp,
+                        pid: e.participant_id,
+                    },
+                    Event::ItemSold(e) if e.participant_id == pid => ItemEvent::SELL {
+                        item_id: e.item_id,
+                        timestamp: e.timestamp,
+                        pid: e.participant_id,
+                    },
+                    Event::ItemUndo(e) if e.participant_id == pid => ItemEvent::UNDO {
+                        after_id: e.after_id,
+                        before_id: e.before_id,
+                        gold_gain: e.gold_gain,
+                        timestamp: e.timestamp,
+                        pid: e.participant_id,
+                    },
+                    _ => continue,
+                };
+                out.events.push(item_event);
             }
         }
         out
     }
 
-    fn filter_pids(game: &MatchData) -> [(String, String); 5] {
-        let mut out: [(String, String); 5] = Default::default();
+    fn filter_pids(game: &MatchData, lanes: &Lanes) -> [(String, String); 5] {
+        Self::lane_players(game, lanes, |p| p.puuid.clone())
+    }
 
-        (0..5).for_each(|i| {
-            out[i] = (
-                game.info.participants.get(i).unwrap().puuid.clone(),
-                game.info.participants.get(i + 5).unwrap().puuid.clone(),
-            );
-        });
+    fn filter_champs(game: &MatchData, lanes: &Lanes) -> [(String, String); 5] {
+        Self::lane_players(game, lanes, |p| p.champion_name.clone())
+    }
 
-        out
+    fn filter_checkpoints(game_tl: &Timeline, lanes: &Lanes, minutes: &[u32]) -> Vec<Checkpoint> {
+        minutes
+            .iter()
+            .map(|&minute| {
+                let mut checkpoint = Checkpoint {
+                    minute,
+                    ..Default::default()
+                };
+                // Frames are a minute apart; the last is wherever the game ended, which for a
+                // game just short of `minute` sits where the checkpoint's frame would be.
+                let Some(frame) = game_tl
+                    .info
+                    .frames
+                    .get(minute as usize)
+                    .filter(|frame| frame.timestamp >= checkpoint.timestamp())
+                else {
+                    return checkpoint;
+                };
+                let per_lane = |stat: fn(&ParticipantFrame) -> i64| {
+                    Some(std::array::from_fn(|i| {
+                        Self::lane_pair(&frame.participant_frames, lanes.ids(i), |p| stat(p) as i32)
+                    }))
+                };
+                checkpoint.lanes = per_lane(|p| p.current_gold);
+                checkpoint.xp = per_lane(|p| p.xp);
+                checkpoint.cs = per_lane(ParticipantFrame::cs);
+                checkpoint.level = per_lane(|p| p.level);
+                checkpoint
+            })
+            .collect()
     }
 
-    fn filter_champs(game: &MatchData) -> [(String, String); 5] {
-        let mut out: [(String, String); 5] = Default::default();
-        (0..5).for_each(|i| {
-            out[i] = (
-                game.info.participants.get(i).unwrap().champion_name.clone(),
-                game.info
-                    .participants
-                    .get(i + 5)
-                    .unwrap()
-                    .champion_name
-                    .clone(),
-            );
-        });
-        out
+    fn filter_series(game_tl: &Timeline, lanes: &Lanes, me: &Me) -> LaneSeries {
+        let (blue, red) = lanes.ids(Games::get_index_from_pos(&me.pos));
+        let (mine, theirs) = match me.side {
+            Side::BLUE => (blue, red),
+            Side::RED => (red, blue),
+        };
+        LaneSeries {
+            timestamps: game_tl.info.frames.iter().map(|f| f.timestamp).collect(),
+            me: Self::player_series(game_tl, mine),
+            opponent: Self::player_series(game_tl, theirs),
+        }
     }
 
-    fn filter_g15(game_tl: &Timeline) -> [(i32, i32); 5] {
-        let frame = game_tl.info.frames[15].participant_frames.clone();
-        let mut out: [(i32, i32); 5] = [(0, 0); 5];
-        (0..5).for_each(|i| {
-            let (p1_gold, p2_gold) = Self::find_gold_in_frame(&frame, i);
-            out[i as usize] = (p1_gold, p2_gold);
-        });
-        out
+    /// Participant `id`'s running totals at every frame, 0 for frames they're missing from.
+    fn player_series(game_tl: &Timeline, id: i64) -> StatSeries {
+        let per_frame = |stat: fn(&ParticipantFrame) -> i64| -> Vec<i32> {
+            game_tl
+                .info
+                .frames
+                .iter()
+                .map(|f| f.participant_frames.participant(id).map_or(0, stat) as i32)
+                .collect()
+        };
+        StatSeries {
+            gold: per_frame(|p| p.total_gold),
+            xp: per_frame(|p| p.xp),
+            cs: per_frame(ParticipantFrame::cs),
+            damage: per_frame(|p| p.damage_stats.total_damage_done_to_champions),
+            level: per_frame(|p| p.level),
+        }
     }
 
-    fn filter_csm(game_tl: &Timeline) -> [(f32, f32); 5] {
+    fn filter_csm(game_tl: &Timeline, lanes: &Lanes) -> [(f32, f32); 5] {
         let mut out: [(f32, f32); 5] = [(0.0, 0.0); 5];
         let frame_count = game_tl.info.frames.len() - 2;
-        for i in 0..5 {
-            let (p1_cs, p2_cs) = Self::find_cs_in_frame(
-                &game_tl
-                    .info
-                    .frames
-                    .get(frame_count)
-                    .unwrap()
-                    .participant_frames,
-                i,
-            );
-            out[i as usize] = (p1_cs / (frame_count as f32), p2_cs / (frame_count as f32));
+        let frame = &game_tl
+            .info
+            .frames
+            .get(frame_count)
+            .unwrap()
+            .participant_frames;
+        for (i, slot) in out.iter_mut().enumerate() {
+            let (p1_cs, p2_cs) = Self::lane_pair(frame, lanes.ids(i), |p| p.cs() as f32);
+            *slot = (p1_cs / (frame_count as f32), p2_cs / (frame_count as f32));
         }
 
         out
     }
 
-    fn filter_dpm(game_tl: &Timeline) -> [(f32, f32); 5] {
+    fn filter_dpm(game_tl: &Timeline, lanes: &Lanes) -> [(f32, f32); 5] {
         let mut out: [(f32, f32); 5] = [(0.0, 0.0); 5];
         let frame_count = game_tl.info.frames.len() - 2;
-        for i in 0..5 {
-            let (p1_cs, p2_cs) = Self::find_damage_in_frame(
-                &game_tl
-                    .info
-                    .frames
-                    .get(frame_count)
-                    .unwrap()
-                    .participant_frames,
-                i,
-            );
-            out[i as usize] = (p1_cs / (frame_count as f32), p2_cs / (frame_count as f32));
+        let frame = &game_tl
+            .info
+            .frames
+            .get(frame_count)
+            .unwrap()
+            .participant_frames;
+        for (i, slot) in out.iter_mut().enumerate() {
+            let (p1_dmg, p2_dmg) = Self::lane_pair(frame, lanes.ids(i), |p| {
+                p.damage_stats.total_damage_done_to_champions as f32
+            });
+            *slot = (p1_dmg / (frame_count as f32), p2_dmg / (frame_count as f32));
         }
         out
     }
 
-    fn filter_kp(game: &MatchData) -> [(f32, f32); 5] {
-        let mut out: [(f32, f32); 5] = [(0.0, 0.0); 5];
-        let mut b_team_kills = 0;
-        let mut r_team_kills = 0;
-        let participants = &game.info.participants;
-
-        for i in 0..5 {
-            let j = i + 5;
-            b_team_kills += participants.get(i).unwrap().kills;
-            r_team_kills += participants.get(j).unwrap().kills;
-        }
-
-        (0..5).for_each(|i| {
-            let p1_kp = ((participants.get(i).unwrap().kills + participants.get(i).unwrap().assists)
-                as f32)
-                / (b_team_kills as f32)
-                * 100.0;
-            let p2_kp = ((participants.get(i + 5).unwrap().kills
-                + participants.get(i + 5).unwrap().assists) as f32)
-                / (r_team_kills as f32)
-                * 100.0;
-            out[i] = (p1_kp, p2_kp);
-        });
-
-        out
+    fn filter_kp(game: &MatchData, lanes: &Lanes) -> [(f32, f32); 5] {
+        let takedowns = Self::lane_players(game, lanes, |p| (p.kills, p.kills + p.assists));
+        let b_team_kills: i64 = takedowns.iter().map(|(b, _)| b.0).sum();
+        let r_team_kills: i64 = takedowns.iter().map(|(_, r)| r.0).sum();
+
+        takedowns.map(|(b, r)| {
+            (
+                b.1 as f32 / b_team_kills as f32 * 100.0,
+                r.1 as f32 / r_team_kills as f32 * 100.0,
+            )
+        })
     }
 
     fn find_wl(game: &MatchData) -> (bool, bool) {
@@ -223,90 +262,28 @@ impl RawData {
         )
     }
 
-    fn find_gold_in_frame(frame: &ParticipantFrames, p_index: i8) -> (i32, i32) {
-        let mut frame_map = HashMap::new();
-        let p_index2 = p_index + 5;
-
-        frame_map.insert(0, frame.n1.current_gold);
-        frame_map.insert(1, frame.n2.current_gold);
-        frame_map.insert(2, frame.n3.current_gold);
-        frame_map.insert(3, frame.n4.current_gold);
-        frame_map.insert(4, frame.n5.current_gold);
-        frame_map.insert(5, frame.n6.current_gold);
-        frame_map.insert(6, frame.n7.current_gold);
-        frame_map.insert(7, frame.n8.current_gold);
-        frame_map.insert(8, frame.n9.current_gold);
-        frame_map.insert(9, frame.n10.current_gold);
-
-        let g1 = match frame_map.get(&p_index) {
-            Some(&num) => num,
-            _ => 0,
-        };
-
-        let g2 = match frame_map.get(&p_index2) {
-            Some(&num) => num,
-            _ => 0,
-        };
-
-        (g1 as i32, g2 as i32)
+    /// `stat` for the blue and red player in each lane. Anyone missing from the match counts
+    /// as the default.
+    fn lane_players<T: Default>(
+        game: &MatchData,
+        lanes: &Lanes,
+        stat: impl Fn(&Participant) -> T,
+    ) -> [(T, T); 5] {
+        let get = |i: usize| game.info.participants.get(i).map(&stat).unwrap_or_default();
+        std::array::from_fn(|slot| {
+            let (blue, red) = lanes.pair(slot);
+            (get(blue), get(red))
+        })
     }
 
-    fn find_cs_in_frame(frame: &ParticipantFrames, p_index: i8) -> (f32, f32) {
-        let mut frame_map: HashMap<i8, i64> = HashMap::new();
-        let p_index2 = p_index + 5;
-
-        frame_map.insert(0, frame.n1.minions_killed + frame.n1.jungle_minions_killed);
-        frame_map.insert(1, frame.n2.minions_killed + frame.n2.jungle_minions_killed);
-        frame_map.insert(2, frame.n3.minions_killed + frame.n3.jungle_minions_killed);
-        frame_map.insert(3, frame.n4.minions_killed + frame.n4.jungle_minions_killed);
-        frame_map.insert(4, frame.n5.minions_killed + frame.n5.jungle_minions_killed);
-        frame_map.insert(5, frame.n6.minions_killed + frame.n6.jungle_minions_killed);
-        frame_map.insert(6, frame.n7.minions_killed + frame.n7.jungle_minions_killed);
-        frame_map.insert(7, frame.n8.minions_killed + frame.n8.jungle_minions_killed);
-        frame_map.insert(8, frame.n9.minions_killed + frame.n9.jungle_minions_killed);
-        frame_map.insert(
-            9,
-            frame.n10.minions_killed + frame.n10.jungle_minions_killed,
-        );
-
-        let cs1 = match frame_map.get(&p_index) {
-            Some(&num) => num,
-            _ => 0,
-        };
-
-        let cs2 = match frame_map.get(&p_index2) {
-            Some(&num) => num,
-            _ => 0,
-        };
-
-        (cs1 as f32, cs2 as f32)
-    }
-
-    fn find_damage_in_frame(frame: &ParticipantFrames, p_index: i8) -> (f32, f32) {
-        let mut frame_map: HashMap<i8, i64> = HashMap::new();
-        let p_index2 = p_index + 5;
-
-        frame_map.insert(0, frame.n1.damage_stats.total_damage_done_to_champions);
-        frame_map.insert(1, frame.n2.damage_stats.total_damage_done_to_champions);
-        frame_map.insert(2, frame.n3.damage_stats.total_damage_done_to_champions);
-        frame_map.insert(3, frame.n4.damage_stats.total_damage_done_to_champions);
-        frame_map.insert(4, frame.n5.damage_stats.total_damage_done_to_champions);
-        frame_map.insert(5, frame.n6.damage_stats.total_damage_done_to_champions);
-        frame_map.insert(6, frame.n7.damage_stats.total_damage_done_to_champions);
-        frame_map.insert(7, frame.n8.damage_stats.total_damage_done_to_champions);
-        frame_map.insert(8, frame.n9.damage_stats.total_damage_done_to_champions);
-        frame_map.insert(9, frame.n10.damage_stats.total_damage_done_to_champions);
-
-        let d1 = match frame_map.get(&p_index) {
-            Some(&num) => num,
-            _ => 0,
-        };
-
-        let d2 = match frame_map.get(&p_index2) {
-            Some(&num) => num,
-            _ => 0,
-        };
-
-        (d1 as f32, d2 as f32)
+    /// `stat` for the blue and red player with timeline participant ids `ids`. Anyone missing
+    /// from the frame counts as the default.
+    fn lane_pair<T: Default>(
+        frame: &ParticipantFrames,
+        (blue, red): (i64, i64),
+        stat: impl Fn(&ParticipantFrame) -> T,
+    ) -> (T, T) {
+        let get = |id: i64| frame.participant(id).map(&stat).unwrap_or_default();
+        (get(blue), get(red))
     }
 }
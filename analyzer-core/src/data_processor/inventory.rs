@@ -0,0 +1,414 @@
+use crate::data_processor::items::Items;
+use crate::data_processor::{ItemEvent, ItemHistory};
+
+/// What a player was carrying at some point in a game, worked out by replaying their item
+/// events.
+///
+/// The timeline only tells half the story: buying an upgrade destroys its components, but
+/// quest rewards, transforms and broken actives also destroy an item and hand over another
+/// without any purchase. Events that happen at the same moment are taken together so the two
+/// can be told apart; see [`Items::silent_upgrade`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Inventory {
+    /// Ids of the items held, once per copy, in the order they were picked up.
+    held: Vec<String>,
+    /// Whether a role quest that upgrades boots to tier 3 has been completed.
+    boots_quest: bool,
+    /// Each purchase with the components it used up, latest last, so an undo can give them
+    /// back.
+    purchases: Vec<(String, Vec<String>)>,
+}
+
+impl Inventory {
+    /// The inventory as it stood at `until` (game milliseconds), including anything that
+    /// happened at exactly that moment. Items the catalog doesn't know are left out.
+    pub fn at(history: &ItemHistory, items: &Items, until: i64) -> Self {
+        let mut events: Vec<&ItemEvent> = history
+            .events
+            .iter()
+            .filter(|e| !matches!(e, ItemEvent::UNKNOWN) && e.get_timestamp() <= until)
+            .collect();
+        events.sort_by_key(|e| e.get_timestamp());
+
+        let mut inventory = Self::default();
+        for moment in events.chunk_by(|a, b| a.get_timestamp() == b.get_timestamp()) {
+            inventory.apply(moment, items);
+        }
+        inventory
+    }
+
+    pub fn held(&self) -> &[String] {
+        &self.held
+    }
+
+    /// What everything held cost, by total price.
+    pub fn value(&self, items: &Items) -> i64 {
+        self.held
+            .iter()
+            .filter_map(|id| items.get(id.clone()))
+            .map(|item| item.get_item_value())
+            .sum()
+    }
+
+    /// Applies the events that happened at one moment. Undos and purchases go first, so
+    /// whatever was destroyed can be checked against what was just bought.
+    fn apply(&mut self, moment: &[&ItemEvent], items: &Items) {
+        let known = |id: &i64| items.get(id.to_string()).is_some();
+        let mut bought: Vec<String> = Vec::new();
+        for event in moment {
+            match event {
+                ItemEvent::UNDO {
+                    after_id,
+                    before_id,
+                    ..
+                } => self.undo(*before_id, *after_id, &known),
+                ItemEvent::PURCHASE { item_id, .. } if known(item_id) => {
+                    self.held.push(item_id.to_string());
+                    self.purchases.push((item_id.to_string(), Vec::new()));
+                    bought.push(item_id.to_string());
+                }
+                _ => {}
+            }
+        }
+
+        // The quest item is destroyed as it's completed, alongside the boots it upgrades.
+        self.boots_quest |= moment.iter().any(|event| match event {
+            ItemEvent::DESTROY { item_id, .. } => items
+                .get(item_id.to_string())
+                .is_some_and(|item| item.upgrades_boots()),
+            _ => false,
+        });
+
+        for event in moment {
+            match event {
+                ItemEvent::DESTROY { item_id, .. } => {
+                    self.destroy(&item_id.to_string(), &bought, items);
+                }
+                ItemEvent::SELL { item_id, .. } => {
+                    self.remove(&item_id.to_string());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn destroy(&mut self, id: &str, bought: &[String], items: &Items) {
+        if !self.remove(id) {
+            return;
+        }
+        if let Some(result) = bought.iter().find(|result| items.builds_into(id, result)) {
+            if let Some((_, consumed)) = self.purchases.iter_mut().rfind(|(p, _)| p == result) {
+                consumed.push(id.to_owned());
+            }
+            return;
+        }
+        if let Some(upgrade) = items.silent_upgrade(id, self.boots_quest) {
+            self.held.push(upgrade);
+        }
+    }
+
+    /// Reverses a purchase (`after` is 0) or a sale (`before` is 0). The gold that comes back
+    /// is already in the player's current gold, so it isn't counted here.
+    fn undo(&mut self, before: i64, after: i64, known: &impl Fn(&i64) -> bool) {
+        if before != 0 && known(&before) {
+            let before = before.to_string();
+            self.remove(&before);
+            if let Some(i) = self.purchases.iter().rposition(|(p, _)| *p == before) {
+                let (_, consumed) = self.purchases.remove(i);
+                self.held.extend(consumed);
+            }
+        }
+        if after != 0 && known(&after) {
+            self.held.push(after.to_string());
+        }
+    }
+
+    /// Drops one copy of `id`, returning whether there was one.
+    fn remove(&mut self, id: &str) -> bool {
+        match self.held.iter().rposition(|held| held == id) {
+            Some(i) => {
+                self.held.remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    const PID: i64 = 3;
+
+    fn item(name: &str, total: i64, from: &[&str], into: &[&str]) -> Value {
+        json!({
+            "name": name,
+            "description": "",
+            "image": { "sprite": "item0.png" },
+            "gold": { "total": total, "purchasable": true },
+            "from": from,
+            "into": into,
+            "tags": [],
+        })
+    }
+
+    /// A few items from the real catalog, cut down to what the rules look at.
+    fn catalog() -> Items {
+        let mut data = serde_json::Map::new();
+        let mut add = |id: &str, value: Value| {
+            data.insert(id.to_owned(), value);
+        };
+        add("1052", item("Amplifying Tome", 400, &[], &["2420", "3003"]));
+        add("1029", item("Cloth Armor", 300, &[], &["2420"]));
+        add("1058", item("Needlessly Large Rod", 1200, &[], &["3157"]));
+        add("1082", item("Dark Seal", 350, &[], &[]));
+        add("2003", item("Health Potion", 50, &[], &[]));
+        add(
+            "2420",
+            item(
+                "Seeker's Armguard",
+                1600,
+                &["1052", "1029", "1052"],
+                &["3157"],
+            ),
+        );
+        add(
+            "2421",
+            item(
+                "Shattered Armguard",
+                1600,
+                &["1052", "1029", "1052"],
+                &["3157"],
+            ),
+        );
+        add(
+            "3157",
+            item("Zhonya's Hourglass", 3250, &["2420", "1058"], &[]),
+        );
+        add("3003", item("Archangel's Staff", 2900, &["1052"], &[]));
+        let mut seraphs = item("Seraph's Embrace", 2900, &[], &[]);
+        seraphs["specialRecipe"] = json!(3003);
+        seraphs["gold"]["purchasable"] = json!(false);
+        add("3040", seraphs);
+
+        let mut boots = item("Boots", 300, &[], &["3009", "3111"]);
+        boots["tags"] = json!(["Boots"]);
+        add("1001", boots);
+        let mut swiftness = item("Boots of Swiftness", 1000, &["1001"], &["3170"]);
+        swiftness["tags"] = json!(["Boots"]);
+        add("3009", swiftness);
+        let mut treads = item("Mercury's Treads", 1250, &["1001", "1033"], &["3173"]);
+        treads["tags"] = json!(["Boots"]);
+        add("3111", treads);
+        let mut swiftmarch = item("Swiftmarch", 1000, &["3009"], &[]);
+        swiftmarch["tags"] = json!(["Boots"]);
+        add("3170", swiftmarch);
+        let mut crushers = item("Chainlaced Crushers", 1250, &["3111"], &[]);
+        crushers["tags"] = json!(["Boots"]);
+        add("3173", crushers);
+        let mut quest = item("Mid Lane Quest", 0, &[], &[]);
+        quest["description"] = json!("<li>Upgrade boots to tier 3<li>Enhanced Recall");
+        add("1201", quest);
+
+        let items = Items::from_data_dragon(&json!({ "data": data })).unwrap();
+        assert!(items.rejected().is_empty(), "{:?}", items.rejected());
+        items
+    }
+
+    fn buy(item_id: i64, timestamp: i64) -> ItemEvent {
+        ItemEvent::PURCHASE {
+            item_id,
+            timestamp,
+            pid: PID,
+        }
+    }
+
+    fn sell(item_id: i64, timestamp: i64) -> ItemEvent {
+        ItemEvent::SELL {
+            item_id,
+            timestamp,
+            pid: PID,
+        }
+    }
+
+    fn destroy(item_id: i64, timestamp: i64) -> ItemEvent {
+        ItemEvent::DESTROY {
+            item_id,
+            timestamp,
+            pid: PID,
+        }
+    }
+
+    fn undo(before_id: i64, after_id: i64, gold_gain: i64, timestamp: i64) -> ItemEvent {
+        ItemEvent::UNDO {
+            after_id,
+            before_id,
+            gold_gain,
+            timestamp,
+            pid: PID,
+        }
+    }
+
+    fn history(events: Vec<ItemEvent>) -> ItemHistory {
+        ItemHistory { events }
+    }
+
+    fn held_at(history: &ItemHistory, items: &Items, until: i64) -> Vec<String> {
+        let mut held = Inventory::at(history, items, until).held().to_vec();
+        held.sort();
+        held
+    }
+
+    #[test]
+    fn upgrade_consumes_its_components() {
+        let items = catalog();
+        // As the timeline reports it: the components go at the moment the upgrade is bought,
+        // listed before the purchase.
+        let history = history(vec![
+            buy(1052, 1_000),
+            buy(1052, 2_000),
+            buy(1029, 3_000),
+            buy(2003, 3_000),
+            destroy(1052, 4_000),
+            destroy(1029, 4_000),
+            destroy(1052, 4_000),
+            buy(2420, 4_000),
+        ]);
+
+        assert_eq!(
+            held_at(&history, &items, 3_000),
+            ["1029", "1052", "1052", "2003"]
+        );
+        let inventory = Inventory::at(&history, &items, 4_000);
+        assert_eq!(inventory.held(), ["2003", "2420"]);
+        assert_eq!(inventory.value(&items), 1650);
+    }
+
+    #[test]
+    fn components_bought_through_an_intermediate_are_consumed() {
+        let items = catalog();
+        let history = history(vec![
+            buy(1052, 1_000),
+            buy(1058, 2_000),
+            destroy(1052, 3_000),
+            destroy(1058, 3_000),
+            buy(3157, 3_000),
+        ]);
+        assert_eq!(held_at(&history, &items, 3_000), ["3157"]);
+    }
+
+    #[test]
+    fn snapshot_ignores_later_events() {
+        let items = catalog();
+        let history = history(vec![buy(1082, 1_000), sell(1082, 5_000)]);
+        assert_eq!(held_at(&history, &items, 4_999), ["1082"]);
+        assert!(held_at(&history, &items, 5_000).is_empty());
+    }
+
+    #[test]
+    fn undone_purchase_gives_components_back_without_counting_gold() {
+        let items = catalog();
+        let history = history(vec![
+            buy(1052, 1_000),
+            buy(1052, 1_000),
+            buy(1029, 1_000),
+            destroy(1052, 2_000),
+            destroy(1029, 2_000),
+            destroy(1052, 2_000),
+            buy(2420, 2_000),
+            undo(2420, 0, 500, 3_000),
+        ]);
+        let inventory = Inventory::at(&history, &items, 3_000);
+        let mut held = inventory.held().to_vec();
+        held.sort();
+        assert_eq!(held, ["1029", "1052", "1052"]);
+        assert_eq!(inventory.value(&items), 1100);
+    }
+
+    #[test]
+    fn undone_sale_puts_the_item_back() {
+        let items = catalog();
+        let history = history(vec![
+            buy(1082, 1_000),
+            sell(1082, 2_000),
+            undo(0, 1082, -140, 2_500),
+        ]);
+        let inventory = Inventory::at(&history, &items, 3_000);
+        assert_eq!(inventory.held(), ["1082"]);
+        assert_eq!(inventory.value(&items), 350);
+    }
+
+    #[test]
+    fn mid_quest_upgrades_boots_held_at_completion() {
+        let items = catalog();
+        let history = history(vec![
+            buy(1001, 1_000),
+            destroy(1001, 2_000),
+            buy(3111, 2_000),
+            destroy(1201, 3_000),
+            destroy(3111, 3_000),
+        ]);
+        assert_eq!(held_at(&history, &items, 2_000), ["3111"]);
+        assert_eq!(held_at(&history, &items, 3_000), ["3173"]);
+    }
+
+    #[test]
+    fn mid_quest_upgrades_boots_bought_afterwards() {
+        let items = catalog();
+        let history = history(vec![
+            buy(1001, 1_000),
+            destroy(1201, 2_000),
+            destroy(1001, 3_000),
+            destroy(3009, 3_000),
+            buy(3009, 3_000),
+        ]);
+        assert_eq!(held_at(&history, &items, 3_000), ["3170"]);
+    }
+
+    #[test]
+    fn boots_destroyed_without_the_quest_are_gone() {
+        let items = catalog();
+        let history = history(vec![buy(3009, 1_000), destroy(3009, 2_000)]);
+        assert!(held_at(&history, &items, 2_000).is_empty());
+    }
+
+    #[test]
+    fn transform_keeps_its_value() {
+        let items = catalog();
+        let history = history(vec![buy(3003, 1_000), destroy(3003, 2_000)]);
+        let inventory = Inventory::at(&history, &items, 2_000);
+        assert_eq!(inventory.held(), ["3040"]);
+        assert_eq!(inventory.value(&items), 2900);
+    }
+
+    #[test]
+    fn broken_item_leaves_its_twin_which_still_builds() {
+        let items = catalog();
+        let history = history(vec![
+            buy(2420, 1_000),
+            buy(1058, 1_000),
+            destroy(2420, 2_000),
+            destroy(1058, 3_000),
+            destroy(2421, 3_000),
+            buy(3157, 3_000),
+        ]);
+        assert_eq!(held_at(&history, &items, 2_000), ["1058", "2421"]);
+        assert_eq!(held_at(&history, &items, 3_000), ["3157"]);
+    }
+
+    #[test]
+    fn consumables_and_unknown_items_drop_out() {
+        let items = catalog();
+        let history = history(vec![
+            buy(2003, 1_000),
+            buy(2003, 1_000),
+            destroy(2003, 2_000),
+            buy(9999, 2_000),
+            destroy(2001, 2_000),
+        ]);
+        assert_eq!(held_at(&history, &items, 2_000), ["2003"]);
+    }
+}
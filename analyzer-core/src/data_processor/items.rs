@@ -6,63 +6,81 @@
 use crate::api_error::ApiError;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
 
 #[derive(Default, Clone)]
 pub struct Items {
     items: HashMap<String, Item>,
+    /// Why each entry that couldn't be read was left out.
+    rejected: Vec<String>,
+    /// Why the catalog couldn't be read at all, if it couldn't.
+    load_error: Option<String>,
 }
 
 #[derive(Default, PartialEq, Eq, Clone)]
 pub struct Item {
     name: String,
     into: Option<Vec<String>>,
+    /// Items this one is built from, repeats included.
+    from: Vec<String>,
+    tags: Vec<String>,
     sprite_url: String,
     total_price: i64,
     special_recipe: Option<String>,
     transforms: bool,
-    //    boots: bool,
+    /// Whether it can be bought in the shop, rather than only granted by the game.
+    purchasable: bool,
+    /// A role quest whose reward upgrades the player's boots to tier 3.
+    upgrades_boots: bool,
 }
 
 impl Item {
-    pub fn new(data: &Value) -> Self {
-        Self {
+    /// Reads item `id` from its Data Dragon entry, failing if it has no name, sprite, price or
+    /// description.
+    pub fn new(id: &str, data: &Value) -> Result<Self, ApiError> {
+        let missing = |field: &str| ApiError::new(&format!("item {id} has no {field}"));
+        let description = data
+            .get("description")
+            .and_then(Value::as_str)
+            .ok_or_else(|| missing("description"))?;
+        Ok(Self {
             name: data
                 .get("name")
-                .expect("Item does not have a name")
-                .to_string(),
+                .and_then(Value::as_str)
+                .ok_or_else(|| missing("name"))?
+                .to_owned(),
             into: data.get("into").and_then(|val| val.as_array()).map(|arr| {
                 arr.iter()
                     .filter_map(|v| v.as_str().map(String::from))
                     .collect()
             }),
+            from: strings(data.get("from")),
+            tags: strings(data.get("tags")),
             sprite_url: data
                 .get("image")
                 .and_then(|val| val.get("sprite"))
-                .expect("Item does not have sprite")
-                .to_string(),
+                .and_then(Value::as_str)
+                .ok_or_else(|| missing("sprite"))?
+                .to_owned(),
             total_price: data
                 .get("gold")
                 .and_then(|val| val.get("total"))
-                .expect("Item does not have price")
-                .as_i64()
+                .and_then(Value::as_i64)
+                .ok_or_else(|| missing("price"))?,
+            // Data Dragon writes it as a number, unlike the ids in ``from`` and ``into``.
+            special_recipe: data.get("specialRecipe").and_then(|val| match val {
+                Value::Number(id) => Some(id.to_string()),
+                Value::String(id) => Some(id.clone()),
+                _ => None,
+            }),
+            transforms: description.contains("<br>Transforms into "),
+            purchasable: data
+                .get("gold")
+                .and_then(|val| val.get("purchasable"))
+                .and_then(Value::as_bool)
                 .unwrap_or_default(),
-            special_recipe: data
-                .get("specialRecipe")
-                .and_then(|val| val.as_str())
-                .map(String::from),
-            transforms: data
-                .get("description")
-                .expect("Could not get item description")
-                .to_string()
-                .contains("<br>Transforms into "),
-            //boots: data
-            //    .get("tags")
-            //    .and_then(|val| val.as_array())
-            //    .map(|arr| {
-            //        arr.iter().position(|x| x == "boots");
-            //    })
-            //    .is_some(),
-        }
+            upgrades_boots: description.contains("Upgrade boots to tier 3"),
+        })
     }
 
     pub fn get_item_value(&self) -> i64 {
@@ -85,59 +103,161 @@ impl Item {
         self.transforms
     }
 
+    pub fn get_from(&self) -> &[String] {
+        &self.from
+    }
+
     pub fn is_boot(&self) -> bool {
-        self.name.contains("Ionian Boots of Lucidity")
-            || self.name.contains("Berserker's Greaves")
-            || self.name.contains("Boots of Swiftness")
-            || self.name.contains("Mercury's Treads")
-            || self.name.contains("Plated Steelcaps")
-            || self.name.contains("Sorcerer's Shoes")
+        self.tags.iter().any(|tag| tag == "Boots")
     }
 
+    /// Whether it builds into anything.
     pub fn is_component(&self) -> bool {
-        !self.is_boot() && !self.transforms
+        self.into.as_ref().is_some_and(|into| !into.is_empty())
     }
+
+    pub fn upgrades_boots(&self) -> bool {
+        self.upgrades_boots
+    }
+}
+
+fn strings(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 /* Generates a hash table of items generated from data dragon items.json
 * Json file should be located in ./assets */
 impl Items {
-    pub fn new() -> Self {
-        let temp = Self::filter_sr_items().unwrap_or_default();
-        Self { items: temp }
+    /// The catalog every valuation uses, read the first time it's asked for. Games are valued
+    /// by the hundred when a profile is rebuilt, and the file is several megabytes. If it can't
+    /// be read, items are simply worth nothing; see [`Items::status`].
+    pub fn shared() -> Arc<Self> {
+        static SHARED: OnceLock<Arc<Items>> = OnceLock::new();
+        Arc::clone(SHARED.get_or_init(|| {
+            Arc::new(Self::load().unwrap_or_else(|e| Self {
+                load_error: Some(e.to_string()),
+                ..Self::default()
+            }))
+        }))
     }
 
-    pub fn get(&self, key: String) -> Option<&Item> {
-        self.items.get(&key)
+    /// Whether the catalog read cleanly. If it didn't, the error says why it couldn't be read
+    /// or which entries were left out, and item values will be off.
+    pub fn status(&self) -> Result<(), ApiError> {
+        if let Some(e) = &self.load_error {
+            return Err(ApiError::new(&format!(
+                "couldn't read the item catalog: {e}"
+            )));
+        }
+        match self.rejected.as_slice() {
+            [] => Ok(()),
+            rejected => Err(ApiError::new(&format!(
+                "left out of the item catalog: {}",
+                rejected.join("; ")
+            ))),
+        }
     }
 
-    pub fn get_unchecked(&self, key: String) -> &Item {
-        //println!("Getting unchecked id {}", key);
-        self.items.get(&key).expect("Failed to get item id, loser")
+    /// Reads ``assets/items.json``. Prefer [`Items::shared`], which only does so once.
+    pub fn load() -> Result<Self, ApiError> {
+        let path = project_root::get_project_root()?.join("assets/items.json");
+        let raw: Value = serde_json::from_str(std::fs::read_to_string(path)?.as_str())?;
+        Self::from_data_dragon(&raw)
     }
 
-    /* Generates the HashTable from DataDragon, but only populates with the items buyable on
-     * summoner's rift */
-    fn filter_sr_items() -> Result<HashMap<String, Item>, ApiError> {
-        let mut out: HashMap<String, Item> = HashMap::new();
-        let path = project_root::get_project_root()?.join("assets/items.json");
-        let raw: serde_json::Value = serde_json::from_str(std::fs::read_to_string(path)?.as_str())?;
-        let items = raw
+    /// Builds the catalog from a Data Dragon ``item.json``, keeping only the items found on
+    /// summoner's rift. Entries that can't be read are left out and listed in
+    /// [`Items::rejected`].
+    pub fn from_data_dragon(raw: &Value) -> Result<Self, ApiError> {
+        let data = raw
             .get("data")
-            .and_then(serde_json::Value::as_object)
-            .expect("Data must be an object");
-
-        for (id, value) in items {
-            if id.len() > 4
-                || id == &String::from("2002")
-                || id == &String::from("2001")
-                || id == &String::from("1201")
-            {
+            .and_then(Value::as_object)
+            .ok_or_else(|| ApiError::new("item catalog has no data object"))?;
+
+        let mut out = Self::default();
+        for (id, value) in data {
+            if id.len() > 4 || id == "2002" || id == "2001" {
                 continue;
             }
-            out.insert(id.clone(), Item::new(value));
+            match Item::new(id, value) {
+                Ok(item) => {
+                    out.items.insert(id.clone(), item);
+                }
+                Err(e) => out.rejected.push(e.to_string()),
+            }
         }
-
         Ok(out)
     }
+
+    pub fn get(&self, key: String) -> Option<&Item> {
+        self.items.get(&key)
+    }
+
+    /// Why each entry that couldn't be read was left out.
+    pub fn rejected(&self) -> &[String] {
+        &self.rejected
+    }
+
+    /// Whether `component` goes into `result`, directly or through one of its components. A
+    /// broken item stands in for its unbroken twin (see [`Items::silent_upgrade`]), so a
+    /// Shattered Armguard goes into Zhonya's Hourglass.
+    pub fn builds_into(&self, component: &str, result: &str) -> bool {
+        let twin = self.twin(component);
+        self.goes_into(component, twin.as_deref(), result)
+    }
+
+    fn goes_into(&self, component: &str, twin: Option<&str>, result: &str) -> bool {
+        self.items.get(result).is_some_and(|item| {
+            item.from.iter().any(|from| {
+                from == component
+                    || Some(from.as_str()) == twin
+                    || self.goes_into(component, twin, from)
+            })
+        })
+    }
+
+    /// What `id` becomes when the game destroys it without it going into a purchase, if
+    /// anything. In order:
+    /// - the item it's the special recipe of, e.g. Archangel's Staff turning into Seraph's
+    ///   Embrace;
+    /// - its one upgrade made from it alone, when that can't be bought (Symbiotic Soles) or
+    ///   when it's boots and `boots_quest` is done (tier 3 boots);
+    /// - the one other item with the same recipe and price, for items that break on use, e.g.
+    ///   Seeker's Armguard leaving a Shattered Armguard behind.
+    pub fn silent_upgrade(&self, id: &str, boots_quest: bool) -> Option<String> {
+        let item = self.items.get(id)?;
+        self.only(|_, other| other.special_recipe.as_deref() == Some(id))
+            .or_else(|| {
+                let upgrade = self.only(|_, other| other.from == [id])?;
+                let upgrade_item = &self.items[&upgrade];
+                (!upgrade_item.purchasable || boots_quest && item.is_boot()).then_some(upgrade)
+            })
+            .or_else(|| self.twin(id))
+    }
+
+    /// The one other item with the same recipe, upgrades and price as `id`.
+    fn twin(&self, id: &str) -> Option<String> {
+        let item = self.items.get(id)?;
+        self.only(|other_id, other| {
+            other_id != id
+                && !item.from.is_empty()
+                && other.from == item.from
+                && other.into == item.into
+                && other.total_price == item.total_price
+        })
+    }
+
+    /// The id of the single item matching `pred`; `None` if there are none or several.
+    fn only(&self, pred: impl Fn(&str, &Item) -> bool) -> Option<String> {
+        let mut matches = self.items.iter().filter(|(id, item)| pred(id, item));
+        let (id, _) = matches.next()?;
+        matches.next().is_none().then(|| id.clone())
+    }
 }
@@ -0,0 +1,352 @@
+use crate::{
+    data_processor::Position,
+    interface::{
+        match_data::{MatchData, Participant},
+        timeline::{ParticipantFrame, Timeline},
+    },
+};
+
+/// Smite's summoner spell id.
+const SMITE: i64 = 11;
+/// Frames past the first few minutes (everyone walking out of base) and before laning ends.
+const LANING_FRAMES: std::ops::RangeInclusive<usize> = 3..=10;
+
+/// Who played which lane on each side, worked out once per game so lane opponents are
+/// compared with each other rather than whoever happens to share their slot in Riot's list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lanes {
+    /// `(blue, red)` indexes into `info.participants`, in [`Position::ALL`] order.
+    pairs: [(usize, usize); 5],
+    /// The same players' timeline participant ids.
+    ids: [(i64, i64); 5],
+}
+
+impl Lanes {
+    /// Places each player by their ``teamPosition``, then their ``individualPosition``, taking
+    /// either only when no teammate claims the same lane. Whoever is left is placed from where
+    /// they spent the laning phase and what they farmed; see [`Lanes::guess`].
+    pub fn resolve(game: &MatchData, game_tl: &Timeline) -> Lanes {
+        let participants = &game.info.participants;
+        let (blue, red): (Vec<usize>, Vec<usize>) =
+            (0..participants.len()).partition(|&i| match participants[i].team_id {
+                100 => true,
+                200 => false,
+                _ => i < 5,
+            });
+        if blue.len() != 5 || red.len() != 5 {
+            return Lanes::default();
+        }
+
+        let blue = Self::resolve_team(participants, &blue, game_tl);
+        let red = Self::resolve_team(participants, &red, game_tl);
+        Lanes {
+            pairs: std::array::from_fn(|i| (blue[i], red[i])),
+            ids: std::array::from_fn(|i| {
+                (
+                    timeline_id(participants, blue[i]),
+                    timeline_id(participants, red[i]),
+                )
+            }),
+        }
+    }
+
+    /// Participant indexes of the blue and red player in lane `slot` (0 to 4).
+    pub fn pair(&self, slot: usize) -> (usize, usize) {
+        self.pairs[slot]
+    }
+
+    /// Timeline participant ids of the blue and red player in lane `slot`.
+    pub fn ids(&self, slot: usize) -> (i64, i64) {
+        self.ids[slot]
+    }
+
+    fn resolve_team(
+        participants: &[Participant],
+        team: &[usize],
+        game_tl: &Timeline,
+    ) -> [usize; 5] {
+        let mut slots: [Option<usize>; 5] = [None; 5];
+        let reported: [fn(&Participant) -> &str; 2] =
+            [|p| &p.team_position, |p| &p.individual_position];
+        for position_of in reported {
+            let unplaced: Vec<usize> = team
+                .iter()
+                .copied()
+                .filter(|i| !slots.contains(&Some(*i)))
+                .collect();
+            let claims: Vec<Option<usize>> = unplaced
+                .iter()
+                .map(|&i| Position::from_riot(position_of(&participants[i])).map(slot_of))
+                .collect();
+            for (&i, claim) in unplaced.iter().zip(&claims) {
+                if let Some(slot) = *claim
+                    && slots[slot].is_none()
+                    && claims.iter().filter(|c| **c == Some(slot)).count() == 1
+                {
+                    slots[slot] = Some(i);
+                }
+            }
+        }
+
+        let open: Vec<usize> = (0..5).filter(|s| slots[*s].is_none()).collect();
+        let unplaced: Vec<usize> = team
+            .iter()
+            .copied()
+            .filter(|i| !slots.contains(&Some(*i)))
+            .collect();
+        let guessed = Self::guess(participants, team, &unplaced, &open, game_tl);
+        for (slot, i) in open.into_iter().zip(guessed) {
+            slots[slot] = Some(i);
+        }
+        slots.map(|slot| slot.unwrap_or_default())
+    }
+
+    /// Fills the `open` lanes with the `unplaced` players, choosing whichever way round costs
+    /// least by [`LaneSignals::cost`]. There are at most 5! ways to try.
+    fn guess(
+        participants: &[Participant],
+        team: &[usize],
+        unplaced: &[usize],
+        open: &[usize],
+        game_tl: &Timeline,
+    ) -> Vec<usize> {
+        let most_cs = team
+            .iter()
+            .map(|&i| {
+                LaneSignals::laning_frame(game_tl, timeline_id(participants, i))
+                    .map_or(0, |f| f.minions_killed)
+            })
+            .max()
+            .unwrap_or_default();
+        let signals: Vec<LaneSignals> = unplaced
+            .iter()
+            .map(|&i| {
+                let id = timeline_id(participants, i);
+                LaneSignals::read(&participants[i], id, game_tl, most_cs)
+            })
+            .collect();
+
+        let mut best = (f32::INFINITY, unplaced.to_vec());
+        let mut order: Vec<usize> = (0..unplaced.len()).collect();
+        permute(&mut order, 0, &mut |order| {
+            let cost = order
+                .iter()
+                .zip(open)
+                .map(|(&p, &slot)| signals[p].cost(slot))
+                .sum::<f32>();
+            if cost < best.0 {
+                best = (cost, order.iter().map(|&p| unplaced[p]).collect());
+            }
+        });
+        best.1
+    }
+}
+
+impl Default for Lanes {
+    /// Riot's own order: participants 1 to 5 top to support on blue, 6 to 10 on red.
+    fn default() -> Self {
+        Lanes {
+            pairs: std::array::from_fn(|i| (i, i + 5)),
+            ids: std::array::from_fn(|i| (i as i64 + 1, i as i64 + 6)),
+        }
+    }
+}
+
+/// What a player's early game says about their lane.
+struct LaneSignals {
+    /// Median of `(y - x)` over the laning frames, scaled to about -1 to 1: top lane runs along
+    /// the top and left edges of the map, bottom lane along the bottom and right, and mid down
+    /// the diagonal between them. Trips to base sit on the diagonal too, hence the median.
+    lean: f32,
+    /// Share of their farm that came from the jungle.
+    jungle_share: f32,
+    /// Their lane minions as a share of the team's highest.
+    cs_share: f32,
+    smite: bool,
+}
+
+impl LaneSignals {
+    const MAP_SIZE: f32 = 15000.0;
+
+    fn read(player: &Participant, id: i64, game_tl: &Timeline, most_cs: i64) -> Self {
+        let mut leans: Vec<f32> = game_tl
+            .info
+            .frames
+            .iter()
+            .skip(*LANING_FRAMES.start())
+            .take(LANING_FRAMES.count())
+            .filter_map(|frame| frame.participant_frames.participant(id))
+            .map(|p| (p.position.y - p.position.x) as f32 / Self::MAP_SIZE)
+            .collect();
+        leans.sort_by(f32::total_cmp);
+        let lean = leans.get(leans.len() / 2).copied().unwrap_or_default();
+
+        let (minions, jungle) = Self::laning_frame(game_tl, id)
+            .map_or((0, 0), |f| (f.minions_killed, f.jungle_minions_killed));
+        LaneSignals {
+            lean,
+            jungle_share: jungle as f32 / (minions + jungle).max(1) as f32,
+            cs_share: minions as f32 / most_cs.max(1) as f32,
+            smite: player.summoner1id == SMITE || player.summoner2id == SMITE,
+        }
+    }
+
+    /// The player's frame at the end of laning, or the last one for shorter timelines.
+    fn laning_frame(game_tl: &Timeline, id: i64) -> Option<&ParticipantFrame> {
+        let frames = &game_tl.info.frames;
+        let last = (*LANING_FRAMES.end()).min(frames.len().checked_sub(1)?);
+        frames[last].participant_frames.participant(id)
+    }
+
+    /// How unlike a player in lane `slot` this player looks. Junglers farm camps and take
+    /// smite; laners stay in their lane, and of the two in bottom lane the carry takes the
+    /// minions.
+    fn cost(&self, slot: usize) -> f32 {
+        let laner = self.jungle_share + if self.smite { 1.0 } else { 0.0 };
+        match Position::ALL[slot] {
+            Position::TOP => (self.lean - 0.5).abs() + laner,
+            Position::JUNGLE => (1.0 - self.jungle_share) + if self.smite { 0.0 } else { 1.0 },
+            Position::MIDDLE => self.lean.abs() + laner,
+            Position::BOTTOM => (self.lean + 0.5).abs() + laner + (1.0 - self.cs_share),
+            Position::SUPPORT => (self.lean + 0.5).abs() + laner + self.cs_share,
+        }
+    }
+}
+
+/// The timeline's id for participant `index`. Matches without ``participantId`` go by Riot's
+/// numbering: from 1, in the order participants are listed.
+fn timeline_id(participants: &[Participant], index: usize) -> i64 {
+    match participants[index].participant_id {
+        0 => index as i64 + 1,
+        id => id,
+    }
+}
+
+fn slot_of(position: Position) -> usize {
+    Position::ALL
+        .iter()
+        .position(|p| *p == position)
+        .unwrap_or_default()
+}
+
+/// Calls `visit` with every ordering of `items[start..]`.
+fn permute(items: &mut Vec<usize>, start: usize, visit: &mut impl FnMut(&[usize])) {
+    if start >= items.len() {
+        visit(items);
+        return;
+    }
+    for i in start..items.len() {
+        items.swap(start, i);
+        permute(items, start + 1, visit);
+        items.swap(start, i);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    const ORDER: [&str; 5] = ["TOP", "JUNGLE", "MIDDLE", "BOTTOM", "UTILITY"];
+
+    fn player(team: i64, id: i64, position: &str) -> Value {
+        json!({ "participantId": id, "teamId": team, "teamPosition": position })
+    }
+
+    /// Both teams in Riot's order, each reporting the lane it played.
+    fn players() -> Vec<Value> {
+        let side = |team: i64, first: i64| {
+            ORDER
+                .iter()
+                .zip(first..)
+                .map(move |(position, id)| player(team, id, position))
+        };
+        side(100, 1).chain(side(200, 6)).collect()
+    }
+
+    fn game(players: Vec<Value>) -> MatchData {
+        serde_json::from_value(json!({ "info": { "participants": players } })).unwrap()
+    }
+
+    /// Eleven one-minute frames with every participant in `frame` at each of them.
+    fn timeline(frame: Value) -> Timeline {
+        let frames: Vec<Value> = (0..=10)
+            .map(|minute| json!({ "timestamp": minute * 60_000, "participantFrames": frame }))
+            .collect();
+        serde_json::from_value(json!({ "info": { "frames": frames } })).unwrap()
+    }
+
+    fn all_ids(lanes: &Lanes) -> [(i64, i64); 5] {
+        std::array::from_fn(|slot| lanes.ids(slot))
+    }
+
+    #[test]
+    fn places_players_by_team_position_whatever_the_list_order() {
+        let mut shuffled = players();
+        shuffled.swap(0, 4);
+        shuffled.swap(6, 8);
+        let lanes = Lanes::resolve(&game(shuffled), &Timeline::default());
+        assert_eq!(
+            std::array::from_fn(|slot| lanes.pair(slot)),
+            [(4, 5), (1, 8), (2, 7), (3, 6), (0, 9)]
+        );
+        assert_eq!(all_ids(&lanes), [(1, 6), (2, 7), (3, 8), (4, 9), (5, 10)]);
+    }
+
+    #[test]
+    fn settles_a_contested_lane_by_individual_position() {
+        let mut players = players();
+        // Top and mid both say mid; their individual positions have them the other way round.
+        players[0]["teamPosition"] = json!("MIDDLE");
+        players[0]["individualPosition"] = json!("MIDDLE");
+        players[2]["individualPosition"] = json!("TOP");
+        let lanes = Lanes::resolve(&game(players), &Timeline::default());
+        assert_eq!(lanes.pair(0), (2, 5));
+        assert_eq!(lanes.pair(2), (0, 7));
+        assert_eq!(lanes.ids(0), (3, 6));
+    }
+
+    #[test]
+    fn places_unreported_players_from_the_laning_phase() {
+        // The jungler is listed first and the top laner second, and neither says which is which.
+        let mut players = players();
+        players.swap(0, 1);
+        for blank in &mut players[..2] {
+            blank["teamPosition"] = json!("");
+            blank["individualPosition"] = json!("Invalid");
+        }
+        players[0]["summoner2Id"] = json!(SMITE);
+        let frame = json!({
+            // Top laner, farming minions up the left edge of the map.
+            "1": {
+                "participantId": 1,
+                "minionsKilled": 60,
+                "position": { "x": 1500, "y": 9000 },
+            },
+            // Jungler, farming camps in between.
+            "2": {
+                "participantId": 2,
+                "jungleMinionsKilled": 40,
+                "position": { "x": 7000, "y": 7500 },
+            },
+        });
+        let lanes = Lanes::resolve(&game(players), &timeline(frame));
+        assert_eq!(lanes.pair(0), (1, 5));
+        assert_eq!(lanes.pair(1), (0, 6));
+        assert_eq!(lanes.ids(0), (1, 6));
+        assert_eq!(lanes.ids(1), (2, 7));
+    }
+
+    #[test]
+    fn takes_timeline_ids_from_participant_id() {
+        // Red side listed first, each team in reverse.
+        let mut players = players();
+        players.rotate_left(5);
+        players[..5].reverse();
+        players[5..].reverse();
+        let lanes = Lanes::resolve(&game(players), &Timeline::default());
+        assert_eq!(all_ids(&lanes), [(1, 6), (2, 7), (3, 8), (4, 9), (5, 10)]);
+        assert_eq!(lanes.pair(0), (9, 4));
+    }
+}
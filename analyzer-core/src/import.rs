@@ -0,0 +1,223 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::api_error::ApiError;
+use crate::data_processor::RawData;
+use crate::interface::game_interface::MatchBatch;
+use crate::interface::match_data::{MatchData, Participant};
+use crate::interface::schema::SchemaDrift;
+use crate::interface::timeline::Timeline;
+use crate::player::{PlayerIdent, Summoner};
+use crate::region::Region;
+use crate::riot_id::RiotId;
+
+/// Whose point of view imported games are analyzed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportTarget {
+    Puuid(String),
+    /// Matched against each participant's Riot ID, ignoring case. The region isn't checked;
+    /// tournament servers report a platform of their own.
+    RiotId(RiotId),
+}
+
+impl ImportTarget {
+    /// A Riot ID if `input` has a ``#`` in it (read for `region`), otherwise a PUUID.
+    pub fn parse(input: &str, region: Region) -> Result<Self, ApiError> {
+        if input.contains('#') {
+            return Ok(Self::RiotId(RiotId::parse(input, region)?));
+        }
+        match input.trim() {
+            "" => Err(ApiError::invalid_input("Enter a Riot ID or a PUUID")),
+            puuid => Ok(Self::Puuid(puuid.to_owned())),
+        }
+    }
+
+    /// The participant in `game` this target names, if they played in it.
+    fn find<'a>(&self, game: &'a MatchData) -> Option<&'a Participant> {
+        game.info.participants.iter().find(|p| match self {
+            ImportTarget::Puuid(puuid) => &p.puuid == puuid,
+            ImportTarget::RiotId(id) => {
+                p.riot_id_game_name.to_lowercase() == id.game_name.to_lowercase()
+                    && p.riot_id_tagline.to_lowercase() == id.tag_line.to_lowercase()
+            }
+        })
+    }
+
+    /// Who the target is, going by how they appear in `game`.
+    fn ident(&self, game: &MatchData) -> Option<PlayerIdent> {
+        let participant = self.find(game)?;
+        let server = match self {
+            ImportTarget::RiotId(id) => id.region,
+            ImportTarget::Puuid(_) => game.info.platform_id.parse().unwrap_or_default(),
+        };
+        Some(PlayerIdent {
+            summoner: Summoner {
+                puuid: participant.puuid.clone(),
+                profile_icon_id: participant.profile_icon,
+                revision_date: game.info.game_end_timestamp,
+                summoner_level: participant.summoner_level,
+            },
+            game_name: participant.riot_id_game_name.clone(),
+            tagline: participant.riot_id_tagline.clone(),
+            server,
+            unknown_server: None,
+        })
+    }
+}
+
+impl fmt::Display for ImportTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportTarget::Puuid(puuid) => write!(f, "{puuid}"),
+            ImportTarget::RiotId(id) => write!(f, "{}", id.name_tag()),
+        }
+    }
+}
+
+/// Games read from disk rather than fetched.
+#[derive(Debug, Default)]
+pub struct Import {
+    /// Every game that was built, and every match that couldn't be. Games the target didn't
+    /// play in, or that ended too early to analyze, are in neither.
+    pub batch: MatchBatch,
+    /// The target as they appear in their most recent imported game.
+    pub ident: Option<PlayerIdent>,
+    /// Where the files strayed from the match and timeline models.
+    pub drift: SchemaDrift,
+}
+
+/// Builds a single game from a match-v5 match file and its timeline, comparing lane opponents
+/// at each of the `checkpoints` minutes.
+pub fn import_pair(
+    match_path: &Path,
+    timeline_path: &Path,
+    target: &ImportTarget,
+    checkpoints: &[u32],
+) -> Result<Option<RawData>, ApiError> {
+    let game: MatchData = read_json(match_path)?;
+    let game_tl: Timeline = read_json(timeline_path)?;
+    build(&game, &game_tl, target, checkpoints)
+}
+
+/// Imports every match under `dir` that has its timeline alongside it. Files are told apart by
+/// what's in them and paired up by match id, so exports named any which way work, as do the
+/// response cache's folders and fixture trees. Anything that isn't a match or timeline is
+/// ignored. Lane opponents are compared at each of the `checkpoints` minutes.
+pub fn import_dir(
+    dir: &Path,
+    target: &ImportTarget,
+    checkpoints: &[u32],
+) -> Result<Import, ApiError> {
+    let mut matches: BTreeMap<String, (PathBuf, Value)> = BTreeMap::new();
+    let mut timelines: BTreeMap<String, (PathBuf, Value)> = BTreeMap::new();
+    let mut results: Vec<(String, Result<Option<RawData>, ApiError>)> = Vec::new();
+
+    for path in json_files(dir)? {
+        let value = match fs::read_to_string(&path)
+            .map_err(ApiError::from)
+            .and_then(|s| serde_json::from_str::<Value>(&s).map_err(ApiError::from))
+        {
+            Ok(value) => value,
+            Err(e) => {
+                results.push((path.display().to_string(), Err(e)));
+                continue;
+            }
+        };
+        let Some(match_id) = value["metadata"]["matchId"].as_str().map(str::to_owned) else {
+            continue;
+        };
+        if value["info"]["frames"].is_array() {
+            timelines.insert(match_id, (path, value));
+        } else if value["info"]["participants"].is_array() {
+            matches.insert(match_id, (path, value));
+        }
+    }
+
+    let mut latest: Option<(i64, PlayerIdent)> = None;
+    let mut drift = SchemaDrift::default();
+    for (match_id, (path, game)) in matches {
+        let Some((tl_path, game_tl)) = timelines.remove(&match_id) else {
+            let error = ApiError::new(&format!("No timeline found for {match_id}"));
+            results.push((match_id, Err(error)));
+            continue;
+        };
+        let result = from_value::<MatchData>(&game, &path).and_then(|parsed| {
+            let parsed_tl = from_value::<Timeline>(&game_tl, &tl_path)?;
+            drift.absorb(SchemaDrift::check(&game, &parsed));
+            drift.absorb(SchemaDrift::check(&game_tl, &parsed_tl));
+
+            let raw = build(&parsed, &parsed_tl, target, checkpoints)?;
+            let end = parsed.info.game_end_timestamp;
+            if raw.is_some() && latest.as_ref().is_none_or(|(newest, _)| end > *newest) {
+                latest = target.ident(&parsed).map(|ident| (end, ident));
+            }
+            Ok(raw)
+        });
+        results.push((match_id, result));
+    }
+
+    Ok(Import {
+        batch: MatchBatch::collect(results),
+        ident: latest.map(|(_, ident)| ident),
+        drift,
+    })
+}
+
+fn build(
+    game: &MatchData,
+    game_tl: &Timeline,
+    target: &ImportTarget,
+    checkpoints: &[u32],
+) -> Result<Option<RawData>, ApiError> {
+    let Some(me) = target.find(game) else {
+        return Ok(None);
+    };
+    if !RawData::can_analyze(game_tl, checkpoints) {
+        return Ok(None);
+    }
+    // Hand-made exports aren't always whole; RawData::new expects two full teams.
+    if game.info.participants.len() != 10 || game.info.teams.len() != 2 {
+        return Err(ApiError::new(&format!(
+            "{} isn't a complete 5v5 game",
+            game.metadata.match_id
+        )));
+    }
+    Ok(Some(RawData::for_player(
+        game,
+        game_tl,
+        checkpoints,
+        &me.puuid,
+    )))
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, ApiError> {
+    let value: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+    from_value(&value, path)
+}
+
+/// Deserializes a file already read as JSON, blaming the file if it doesn't fit `T`.
+fn from_value<T: DeserializeOwned>(value: &Value, path: &Path) -> Result<T, ApiError> {
+    T::deserialize(value).map_err(|source| ApiError::Schema {
+        endpoint: path.display().to_string(),
+        source,
+    })
+}
+
+/// Every ``.json`` file under `dir`, at any depth.
+fn json_files(dir: &Path) -> Result<Vec<PathBuf>, ApiError> {
+    let mut out = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            out.extend(json_files(&path)?);
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            out.push(path);
+        }
+    }
+    Ok(out)
+}
@@ -1,79 +1,293 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-use crate::api_error::ApiError;
+use crate::api_error::{ApiError, ErrorKind};
+use crate::interface::api_key::ApiKey;
+use crate::interface::cache::{CacheKind, ResponseCache};
 use crate::interface::match_data::MatchData;
+use crate::interface::rate_limit::{RateLimiter, sleep};
+use crate::interface::schema::SchemaDrift;
 use crate::interface::timeline::Timeline;
+use crate::interface::transport::{ReqwestTransport, Transport, endpoint};
 use crate::player::PlayerIdent;
+use crate::region::Region;
+use crate::riot_id::RiotId;
 
+pub mod api_key;
+pub mod cache;
 pub mod game_interface;
 pub mod match_data;
 pub mod ranked_data;
+pub mod rate_limit;
+pub mod schema;
 pub mod timeline;
+pub mod transport;
 pub mod user_interface;
 
-#[derive(Deserialize, Serialize, Clone, Default)]
+/// Talks to Riot's API on behalf of one player's shard.
+///
+/// The `_async` methods only truly yield in the browser. Natively the transport, rate-limit
+/// waits and retry back-off all block the thread the future is polled on, so drive them with
+/// `pollster::block_on` on a thread of their own, as the blocking wrappers do, and never on a
+/// shared async executor.
+#[derive(Deserialize, Serialize, Clone)]
 pub struct Interface {
-    api_key: String,
-    server: String,
+    /// Never saved. Loaded profiles pick up [`ApiKey::shared`].
+    #[serde(skip, default = "ApiKey::shared")]
+    api_key: ApiKey,
+    server: Region,
+    /// How requests actually reach Riot. Not saved; a loaded profile talks over reqwest unless
+    /// told otherwise.
+    #[serde(skip, default = "Interface::default_transport")]
+    transport: Arc<dyn Transport>,
+    /// Shared with every other interface in the process unless swapped out; see
+    /// [`RateLimiter::shared`].
+    #[serde(skip, default = "RateLimiter::shared")]
+    limiter: Arc<RateLimiter>,
+    /// Where finished matches and timelines are kept between runs. Nothing is cached unless one
+    /// is set.
+    #[serde(skip)]
+    cache: Option<Arc<ResponseCache>>,
+    /// Every way match and timeline responses have strayed from the models so far. Shared by
+    /// clones, so a whole batch reports into one place.
+    #[serde(skip)]
+    drift: Arc<Mutex<SchemaDrift>>,
+    /// How many matches are fetched and processed at once.
+    #[serde(default = "Interface::default_workers")]
+    workers: usize,
+}
+
+impl Default for Interface {
+    fn default() -> Self {
+        Self {
+            api_key: ApiKey::shared(),
+            server: Region::default(),
+            transport: Self::default_transport(),
+            limiter: RateLimiter::shared(),
+            cache: None,
+            drift: Arc::default(),
+            workers: Self::default_workers(),
+        }
+    }
 }
 
 impl Interface {
+    /// An interface with a key of its own, unaffected by rotating any other.
     pub fn new(api_key: &str) -> Self {
         Self {
-            api_key: api_key.to_owned(),
-            server: String::default(),
+            api_key: ApiKey::new(api_key),
+            ..Default::default()
         }
     }
 
-    pub fn new_with_server(api_key: &str, s: String) -> Self {
+    pub fn new_with_server(api_key: &str, s: Region) -> Self {
         Self {
-            api_key: api_key.to_owned(),
+            api_key: ApiKey::new(api_key),
             server: s,
+            ..Default::default()
         }
     }
 
-    pub fn gen_player_ident_from_string(
-        &mut self,
-        raw_username: &str,
-    ) -> Result<PlayerIdent, ApiError> {
-        let p: Vec<&str> = raw_username.split("#").collect();
-        println!("gen_player: {:?}", p);
-        if p.len() != 3 {
-            return Err(ApiError::new("Incorrect format for inputted player"));
+    pub fn with_server(mut self, server: Region) -> Self {
+        self.server = server;
+        self
+    }
+
+    /// Reads the key from `api_key`, so rotating it there takes effect on the next request.
+    pub fn with_api_key(mut self, api_key: ApiKey) -> Self {
+        self.api_key = api_key;
+        self
+    }
+
+    /// Talks to whatever is listening at `base_url` instead of Riot, e.g. ``mock-riot``.
+    pub fn with_base_url(self, base_url: &str) -> Self {
+        self.with_transport(Arc::new(ReqwestTransport::with_base_url(base_url)))
+    }
+
+    /// Swaps out the transport, e.g. for a [`transport::FixtureTransport`] when working offline.
+    pub fn with_transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    pub fn with_rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    /// Serves match and timeline requests from `cache` when it has them, and fills it when not.
+    pub fn with_cache(mut self, cache: Arc<ResponseCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Caps how many matches are in flight at once. The rate limiter still has the final say on
+    /// how fast requests go out.
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    pub fn rate_limiter(&self) -> Arc<RateLimiter> {
+        Arc::clone(&self.limiter)
+    }
+
+    pub fn transport(&self) -> Arc<dyn Transport> {
+        Arc::clone(&self.transport)
+    }
+
+    pub fn cache(&self) -> Option<Arc<ResponseCache>> {
+        self.cache.clone()
+    }
+
+    /// Fields match and timeline responses were missing, or had that the models don't, since
+    /// this interface was made. Only responses fresh from the network are checked.
+    pub fn schema_drift(&self) -> SchemaDrift {
+        self.drift.lock().expect("Failed mutex operation").clone()
+    }
+
+    /// The key as it stands right now.
+    pub fn api_key(&self) -> String {
+        self.api_key.get()
+    }
+
+    pub fn api_key_handle(&self) -> ApiKey {
+        self.api_key.clone()
+    }
+
+    fn default_transport() -> Arc<dyn Transport> {
+        Arc::new(ReqwestTransport::new())
+    }
+
+    fn default_workers() -> usize {
+        4
+    }
+
+    /// GETs `path` from the given routing host and hands back the body of a successful response.
+    /// `method` names the endpoint for Riot's per-method rate limits. Requests wait their turn in
+    /// the rate limiter, and a 429 is retried once its ``Retry-After`` has passed. Dropped
+    /// connections and 5xx responses are retried too, backing off exponentially between attempts.
+    async fn fetch(&self, host: &str, method: &str, path: &str) -> Result<String, ApiError> {
+        const MAX_ATTEMPTS: u32 = 5;
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            self.limiter.acquire_async(host, method).await;
+            let api_key = self.api_key.get();
+            let error = match self.transport.get_async(host, path, &api_key).await {
+                Ok(resp) => {
+                    self.limiter.record(host, method, &resp);
+                    if resp.is_success() {
+                        return Ok(resp.body);
+                    }
+                    ApiError::Status {
+                        status: resp.status,
+                        endpoint: endpoint(path).to_owned(),
+                    }
+                }
+                Err(e) => e,
+            };
+            if attempt == MAX_ATTEMPTS || !error.is_transient() {
+                return Err(error);
+            }
+            // After a 429 the limiter is already holding off until Retry-After.
+            if error.kind() != ErrorKind::RateLimited {
+                sleep(Self::backoff(attempt)).await;
+            }
         }
-        match p.last() {
-            Some(server) => {
-                self.server = String::from(*server);
-                self.request_player_data(
-                    String::from(*p.first().expect("Could not index player")).replace(" ", "%20"),
-                    String::from(*p.get(1).expect("Could not index player")),
-                )
+    }
+
+    /// How long to wait after the `attempt`th failed try: half a second, doubling each time, up
+    /// to eight.
+    fn backoff(attempt: u32) -> Duration {
+        const BASE: Duration = Duration::from_millis(500);
+        const MAX: Duration = Duration::from_secs(8);
+        BASE.saturating_mul(1 << attempt.saturating_sub(1).min(16)).min(MAX)
+    }
+
+    /// [`Interface::fetch`], then parses the body, blaming the endpoint if it doesn't fit `T`.
+    async fn fetch_json<T: DeserializeOwned>(
+        &self,
+        host: &str,
+        method: &str,
+        path: &str,
+    ) -> Result<T, ApiError> {
+        let body = self.fetch(host, method, path).await?;
+        Self::parse(&body, path)
+    }
+
+    /// [`Interface::fetch_json`] for a single match document, going through the response cache
+    /// if there is one. A cached entry that no longer parses is thrown out and fetched again.
+    /// Documents fresh from the network are checked against their model; see
+    /// [`Interface::schema_drift`]. Cached ones were checked when they were fetched.
+    async fn fetch_cached<T: DeserializeOwned + Serialize>(
+        &self,
+        kind: CacheKind,
+        match_id: &str,
+        host: &str,
+        method: &str,
+        path: &str,
+    ) -> Result<T, ApiError> {
+        let Some(cache) = &self.cache else {
+            let body = self.fetch(host, method, path).await?;
+            return self.parse_checked(&body, path);
+        };
+        if let Some(body) = cache.get(kind, match_id) {
+            match Self::parse(&body, path) {
+                Ok(parsed) => return Ok(parsed),
+                Err(_) => cache.remove(kind, match_id),
             }
-            None => Err(ApiError::new(
-                "Incorrect format for inputted player; not enough fields",
-            )),
         }
-        //self.server = String::from(p[2]);
-        //self.request_player_data(String::from(p[0]).replace(" ", "%20"), String::from(p[1]))
-    }
-
-    pub fn get_server(server: &str) -> &str {
-        match server {
-            "NA" => "americas",
-            "BR" => "americas",
-            "LAS" => "americas",
-            "LAN" => "americas",
-            "KR" => "asia",
-            "JP" => "asia",
-            "EUW" => "europe",
-            "EUNE" => "europe",
-            "ME" => "europe",
-            "TR" => "europe",
-            "RU" => "europe",
-            "OCE" => "sea",
-            "VN" => "sea",
-            "TW" => "sea",
-            &_ => "americas",
+        let body = self.fetch(host, method, path).await?;
+        let parsed = self.parse_checked(&body, path)?;
+        // The document is already in hand; a cache that can't be written to only means asking
+        // Riot again next time.
+        let _: Result<(), ApiError> = cache.put(kind, match_id, &body);
+        Ok(parsed)
+    }
+
+    /// [`Interface::parse`], noting anywhere the body and the model disagree.
+    fn parse_checked<T: DeserializeOwned + Serialize>(
+        &self,
+        body: &str,
+        path: &str,
+    ) -> Result<T, ApiError> {
+        let schema_error = |source| ApiError::Schema {
+            endpoint: endpoint(path).to_owned(),
+            source,
+        };
+        let value: Value = serde_json::from_str(body).map_err(schema_error)?;
+        let parsed = T::deserialize(&value).map_err(schema_error)?;
+
+        let drift = SchemaDrift::check(&value, &parsed);
+        if !drift.is_empty() {
+            self.drift
+                .lock()
+                .expect("Failed mutex operation")
+                .absorb(drift);
         }
+        Ok(parsed)
+    }
+
+    fn parse<T: DeserializeOwned>(body: &str, path: &str) -> Result<T, ApiError> {
+        serde_json::from_str(body).map_err(|source| ApiError::Schema {
+            endpoint: endpoint(path).to_owned(),
+            source,
+        })
+    }
+
+    /// Looks `id` up and points this interface at the player's shard.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn lookup_riot_id(&mut self, id: &RiotId) -> Result<PlayerIdent, ApiError> {
+        pollster::block_on(self.lookup_riot_id_async(id))
+    }
+
+    pub async fn lookup_riot_id_async(&mut self, id: &RiotId) -> Result<PlayerIdent, ApiError> {
+        self.server = id.region;
+        self.request_player_data_async(id).await
     }
 }
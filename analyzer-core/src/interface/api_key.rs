@@ -0,0 +1,91 @@
+use std::fmt;
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
+
+use crate::api_error::{ApiError, ErrorKind};
+use crate::interface::Interface;
+
+/// A Riot API key that can be swapped while requests are in flight. Clones share the key, so
+/// rotating it through any one of them reaches every [`Interface`] holding a clone.
+#[derive(Debug, Clone, Default)]
+pub struct ApiKey(Arc<RwLock<String>>);
+
+impl ApiKey {
+    pub fn new(key: &str) -> Self {
+        Self(Arc::new(RwLock::new(key.trim().to_owned())))
+    }
+
+    /// The key every [`Interface`] uses unless given its own, shared the same way as
+    /// [`RateLimiter::shared`](crate::interface::rate_limit::RateLimiter::shared).
+    pub fn shared() -> Self {
+        static SHARED: OnceLock<ApiKey> = OnceLock::new();
+        SHARED.get_or_init(ApiKey::default).clone()
+    }
+
+    pub fn get(&self) -> String {
+        self.0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub fn set(&self, key: &str) {
+        *self.0.write().unwrap_or_else(PoisonError::into_inner) = key.trim().to_owned();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_empty()
+    }
+}
+
+/// What Riot made of a key. Riot only answers 401 when no key was sent at all; an expired
+/// personal dev key (they last 24 hours), a revoked key and a mistyped one all get the same 403,
+/// so they can't be told apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyStatus {
+    Valid,
+    Rejected,
+    Missing,
+}
+
+impl fmt::Display for KeyStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyStatus::Valid => write!(f, "API key is valid"),
+            KeyStatus::Rejected => write!(f, "API key has expired or is invalid"),
+            KeyStatus::Missing => write!(f, "No API key set"),
+        }
+    }
+}
+
+impl Interface {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn validate_key(&self) -> Result<KeyStatus, ApiError> {
+        pollster::block_on(self.validate_key_async())
+    }
+
+    /// Asks lol-status for the platform's status, which any working key may read, to find out
+    /// whether the current key is any good. Failures that say nothing about the key (network,
+    /// outages) are passed back as errors.
+    pub async fn validate_key_async(&self) -> Result<KeyStatus, ApiError> {
+        if self.api_key.is_empty() {
+            return Ok(KeyStatus::Missing);
+        }
+        let result = self
+            .fetch(
+                self.server.platform(),
+                "lol-status-v4.getPlatformData",
+                "/lol/status/v4/platform-data",
+            )
+            .await;
+        match result {
+            Ok(_) => Ok(KeyStatus::Valid),
+            Err(e) => match e.kind() {
+                ErrorKind::Forbidden => Ok(KeyStatus::Rejected),
+                _ => Err(e),
+            },
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::SystemTime;
+
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+
+use crate::api_error::ApiError;
+
+/// Which of the two per-match documents an entry holds. They're stored apart so a match can be
+/// cached before its timeline has been fetched (or when it turns out not to need one).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheKind {
+    Match,
+    Timeline,
+}
+
+impl CacheKind {
+    fn dir(&self) -> &'static str {
+        match self {
+            CacheKind::Match => "matches",
+            CacheKind::Timeline => "timelines",
+        }
+    }
+}
+
+/// Raw match-v5 JSON on disk, keyed by match id. Finished matches never change, so anything in
+/// here is served instead of asking Riot again, whichever profile first fetched it. Entries live
+/// under ``{root}/matches/`` and ``{root}/timelines/``, optionally gzipped. Once the cache grows
+/// past its size limit the least recently used entries, read or written, are removed first.
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    root: PathBuf,
+    max_bytes: u64,
+    compress: bool,
+    /// Bytes on disk as of the last listing plus whatever has been written since, so a write
+    /// only lists the directories when it might have pushed the cache over. `None` until the
+    /// first write. Shared by clones.
+    total: Arc<Mutex<Option<u64>>>,
+}
+
+impl ResponseCache {
+    /// Timelines run to about a megabyte each, so this holds a few hundred games uncompressed.
+    pub const DEFAULT_MAX_BYTES: u64 = 512 * 1024 * 1024;
+
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            max_bytes: Self::DEFAULT_MAX_BYTES,
+            compress: false,
+            total: Arc::default(),
+        }
+    }
+
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Gzip new entries. Entries already on disk are read either way.
+    pub fn compressed(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    /// The cached body for `match_id`, if there is one that can still be read. A hit counts as a
+    /// use, so games that keep being read are the last to be evicted.
+    pub fn get(&self, kind: CacheKind, match_id: &str) -> Option<String> {
+        let plain = self.path(kind, match_id, false);
+        if let Ok(body) = fs::read_to_string(&plain) {
+            touch(&plain);
+            return Some(body);
+        }
+        let gzipped = self.path(kind, match_id, true);
+        let mut body = String::new();
+        GzDecoder::new(fs::File::open(&gzipped).ok()?)
+            .read_to_string(&mut body)
+            .ok()?;
+        touch(&gzipped);
+        Some(body)
+    }
+
+    /// Stores `body` for `match_id`, then trims the cache back under its size limit.
+    pub fn put(&self, kind: CacheKind, match_id: &str, body: &str) -> Result<(), ApiError> {
+        let path = self.path(kind, match_id, self.compress);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let replaced = fs::metadata(&path).map_or(0, |meta| meta.len());
+        // Written to the side and moved into place so a reader never sees half an entry.
+        let tmp = path.with_extension("tmp");
+        if self.compress {
+            let mut encoder = GzEncoder::new(fs::File::create(&tmp)?, Compression::default());
+            encoder.write_all(body.as_bytes())?;
+            encoder.finish()?;
+        } else {
+            fs::write(&tmp, body)?;
+        }
+        fs::rename(&tmp, &path)?;
+        let written = fs::metadata(&path)?.len();
+
+        let mut total = self.lock_total();
+        let size = match *total {
+            Some(size) => size.saturating_sub(replaced) + written,
+            None => self.size(),
+        };
+        // Left unknown until eviction finishes, so a failure part way has it listed again.
+        *total = None;
+        *total = Some(if size > self.max_bytes {
+            self.evict()?
+        } else {
+            size
+        });
+        Ok(())
+    }
+
+    /// Drops an entry, e.g. one that no longer parses.
+    pub fn remove(&self, kind: CacheKind, match_id: &str) {
+        let mut total = self.lock_total();
+        for compressed in [false, true] {
+            let path = self.path(kind, match_id, compressed);
+            let len = fs::metadata(&path).map_or(0, |meta| meta.len());
+            if fs::remove_file(&path).is_ok()
+                && let Some(total) = total.as_mut()
+            {
+                *total = total.saturating_sub(len);
+            }
+        }
+    }
+
+    /// Total size of every entry, in bytes.
+    pub fn size(&self) -> u64 {
+        self.entries().iter().map(|(_, len, _)| len).sum()
+    }
+
+    /// Removes the least recently used entries until the cache is a tenth under `max_bytes`, so
+    /// the writes that follow don't each have to list the directories again. Returns what's left,
+    /// counted afresh; if the running total had drifted high and the cache actually fits,
+    /// nothing is removed.
+    fn evict(&self) -> Result<u64, ApiError> {
+        let mut entries = self.entries();
+        let mut total: u64 = entries.iter().map(|(_, len, _)| len).sum();
+        if total <= self.max_bytes {
+            return Ok(total);
+        }
+        let target = self.max_bytes - self.max_bytes / 10;
+        entries.sort_by_key(|(_, _, modified)| *modified);
+        for (path, len, _) in entries {
+            if total <= target {
+                break;
+            }
+            match fs::remove_file(&path) {
+                // Another worker got to it first.
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                other => other?,
+            }
+            total = total.saturating_sub(len);
+        }
+        Ok(total)
+    }
+
+    /// Every entry on disk with its size and when it was last used.
+    fn entries(&self) -> Vec<(PathBuf, u64, SystemTime)> {
+        [CacheKind::Match, CacheKind::Timeline]
+            .iter()
+            .filter_map(|kind| fs::read_dir(self.root.join(kind.dir())).ok())
+            .flatten()
+            .filter_map(Result::ok)
+            .filter(|entry| !is_tmp(&entry.path()))
+            .filter_map(|entry| {
+                let meta = entry.metadata().ok()?;
+                let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                Some((entry.path(), meta.len(), modified))
+            })
+            .collect()
+    }
+
+    fn path(&self, kind: CacheKind, match_id: &str, compressed: bool) -> PathBuf {
+        let ext = if compressed { "json.gz" } else { "json" };
+        self.root.join(kind.dir()).join(format!("{match_id}.{ext}"))
+    }
+
+    fn lock_total(&self) -> std::sync::MutexGuard<'_, Option<u64>> {
+        self.total.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn is_tmp(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "tmp")
+}
+
+/// Marks an entry as just used. Eviction goes by modification time, which unlike access time
+/// is kept on every filesystem. Best-effort: a read-only cache still serves its entries.
+fn touch(path: &Path) {
+    let _: io::Result<()> = fs::File::options()
+        .write(true)
+        .open(path)
+        .and_then(|file| file.set_modified(SystemTime::now()));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn age(path: &Path, secs: u64) {
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .and_then(|file| file.set_modified(SystemTime::now() - Duration::from_secs(secs)))
+            .unwrap();
+    }
+
+    #[test]
+    fn evicts_the_least_recently_used_entry() {
+        let root = std::env::temp_dir().join(format!("jade-cache-{}", std::process::id()));
+        let cache = ResponseCache::new(&root).with_max_bytes(250);
+        let body = "x".repeat(100);
+        cache.put(CacheKind::Match, "NA1_1", &body).unwrap();
+        cache.put(CacheKind::Match, "NA1_2", &body).unwrap();
+        age(&cache.path(CacheKind::Match, "NA1_1", false), 20);
+        age(&cache.path(CacheKind::Match, "NA1_2", false), 10);
+
+        // Written first but read since, so the second game is the one to go.
+        assert!(cache.get(CacheKind::Match, "NA1_1").is_some());
+        cache.put(CacheKind::Timeline, "NA1_1", &body).unwrap();
+        let kept = |kind, id| cache.get(kind, id).is_some();
+        let result = (
+            kept(CacheKind::Match, "NA1_1"),
+            kept(CacheKind::Match, "NA1_2"),
+            kept(CacheKind::Timeline, "NA1_1"),
+            cache.size(),
+        );
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(result, (true, false, true, 200));
+    }
+}
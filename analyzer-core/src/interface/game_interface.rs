@@ -3,46 +3,261 @@ use crate::data_processor::RawData;
 use crate::interface::Interface;
 use crate::interface::MatchData;
 use crate::interface::Timeline;
+use crate::interface::cache::CacheKind;
+use crate::queue::QueueType;
 
-use serde::Deserialize;
-use serde_json::Deserializer;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::mpsc;
+#[cfg(not(target_arch = "wasm32"))]
+use threadpool::ThreadPool;
+
+/// Which match ids to ask match-v5 for. Times are unix timestamps in seconds, as the endpoint
+/// expects; `max` caps the number of ids, counting back from the most recent game. An empty
+/// `queues` means every queue.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MatchQuery {
+    pub start_time: Option<i64>,
+    pub end_time: Option<i64>,
+    pub max: Option<usize>,
+    pub queues: Vec<QueueType>,
+}
+
+impl MatchQuery {
+    /// Everything played at or after `start_time`.
+    pub fn since(start_time: i64) -> Self {
+        Self {
+            start_time: Some(start_time),
+            ..Default::default()
+        }
+    }
+
+    /// The `max` most recent games.
+    pub fn last(max: usize) -> Self {
+        Self {
+            max: Some(max),
+            ..Default::default()
+        }
+    }
+
+    pub fn until(mut self, end_time: i64) -> Self {
+        self.end_time = Some(end_time);
+        self
+    }
+
+    pub fn in_queues(mut self, queues: &[QueueType]) -> Self {
+        self.queues = queues.to_vec();
+        self
+    }
+
+    fn time_params(&self) -> String {
+        let mut out = String::new();
+        if let Some(start) = self.start_time {
+            out.push_str(&format!("&startTime={start}"));
+        }
+        if let Some(end) = self.end_time {
+            out.push_str(&format!("&endTime={end}"));
+        }
+        out
+    }
+}
+
+/// What came back from fetching a batch of matches: every game that was built, in the order the
+/// ids were given, and every id that couldn't be.
+#[derive(Debug, Default)]
+pub struct MatchBatch {
+    pub games: Vec<RawData>,
+    pub failures: Vec<MatchFailure>,
+}
+
+#[derive(Debug)]
+pub struct MatchFailure {
+    pub match_id: String,
+    pub error: ApiError,
+}
+
+impl MatchBatch {
+    /// Sorts per-match results, given in id order, into games and failures.
+    pub(crate) fn collect(
+        results: impl IntoIterator<Item = (String, Result<Option<RawData>, ApiError>)>,
+    ) -> Self {
+        let mut out = MatchBatch::default();
+        for (match_id, result) in results {
+            match result {
+                Ok(Some(raw)) => out.games.push(raw),
+                Ok(None) => {}
+                Err(error) => out.failures.push(MatchFailure { match_id, error }),
+            }
+        }
+        out
+    }
+
+    /// Ids that failed for reasons that may pass, worth asking for again later. Matches that
+    /// failed for good, such as one whose timeline Riot no longer has, aren't included.
+    pub fn retry_ids(&self) -> Vec<String> {
+        self.failures
+            .iter()
+            .filter(|f| f.error.is_transient())
+            .map(|f| f.match_id.clone())
+            .collect()
+    }
+
+    /// The built games, unless every single match failed, in which case the first error is
+    /// handed back instead.
+    pub fn into_games(self) -> Result<Vec<RawData>, ApiError> {
+        match self.failures.into_iter().next() {
+            Some(failure) if self.games.is_empty() => Err(failure.error),
+            _ => Ok(self.games),
+        }
+    }
+}
 
 impl Interface {
-    pub fn get_game_ids(
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_game_ids(&self, query: &MatchQuery, puuid: &str) -> Result<Vec<String>, ApiError> {
+        pollster::block_on(self.get_game_ids_async(query, puuid))
+    }
+
+    /// Every match id matching `query`, oldest first. Each queue is asked for separately and the
+    /// results merged; match ids on a shard count up over time, so they sort chronologically.
+    pub async fn get_game_ids_async(
         &self,
-        start_timestamp: &String,
-        puuid: &String,
+        query: &MatchQuery,
+        puuid: &str,
     ) -> Result<Vec<String>, ApiError> {
-        //todo!();
-        let mut ids = Vec::new();
-        let url_server = Self::get_server(&self.server);
-        //println!(
-        //    "https://{}.api.riotgames.com/lol/match/v5/matches/by-puuid/{}/ids?start={}&api_key={}",
-        //    url_server, puuid, start_timestamp, self.api_key
-        //);
-        let q_type = String::from("ranked");
-        let q_val = String::from("420"); /* for later parameterization */
-        let resp = reqwest::blocking::get(
-            format!(
-                "https://{}.api.riotgames.com/lol/match/v5/matches/by-puuid/{}/ids?queue={}&type={}&startTime={}&api_key={}",
-                url_server, puuid, q_val, q_type, start_timestamp, self.api_key)
-            ).unwrap().text().unwrap();
-        println!("{}", resp);
-        let mut deserializer = Deserializer::from_str(&resp);
-        Deserialize::deserialize_in_place(&mut deserializer, &mut ids)?;
+        let mut ids: Vec<String> = Vec::new();
+        if query.queues.is_empty() {
+            ids = self.get_queue_game_ids(query, None, puuid).await?;
+        } else {
+            for queue in &query.queues {
+                ids.extend(self.get_queue_game_ids(query, Some(queue), puuid).await?);
+            }
+        }
+
+        let id_number = |id: &String| {
+            id.rsplit('_')
+                .next()
+                .and_then(|n| n.parse::<u64>().ok())
+                .unwrap_or_default()
+        };
+        ids.sort_by_key(|id| std::cmp::Reverse(id_number(id)));
+        ids.dedup();
+        if let Some(max) = query.max {
+            ids.truncate(max);
+        }
         ids.reverse();
         Ok(ids)
     }
 
+    /// Ids for a single queue, newest first. match-v5 hands ids out at most 100 at a time, so this
+    /// keeps paging until the window is exhausted or `query.max` is reached.
+    async fn get_queue_game_ids(
+        &self,
+        query: &MatchQuery,
+        queue: Option<&QueueType>,
+        puuid: &str,
+    ) -> Result<Vec<String>, ApiError> {
+        const PAGE_SIZE: usize = 100;
+        let url_server = self.server.regional();
+        let queue_param = queue
+            .map(|q| format!("&queue={}", q.id()))
+            .unwrap_or_default();
+
+        let mut ids: Vec<String> = Vec::new();
+        loop {
+            let count = match query.max {
+                Some(max) => PAGE_SIZE.min(max - ids.len()),
+                None => PAGE_SIZE,
+            };
+            let page: Vec<String> = self.fetch_json(
+                url_server,
+                "match-v5.getMatchIdsByPUUID",
+                &format!(
+                    "/lol/match/v5/matches/by-puuid/{puuid}/ids?start={}&count={count}{queue_param}{}",
+                    ids.len(),
+                    query.time_params()
+                ),
+            ).await?;
+
+            let last_page = page.len() < count;
+            ids.extend(page);
+            if last_page || query.max.is_some_and(|max| ids.len() >= max) {
+                break;
+            }
+        }
+        Ok(ids)
+    }
+
+    /// Fetches and processes every match in `ids` on a pool of worker threads. A match that fails
+    /// to download or parse is reported in [`MatchBatch::failures`] rather than sinking the rest
+    /// of the batch. Games that didn't finish normally, or were played in a mode their queue
+    /// doesn't analyze, are skipped. Lane opponents are compared at each of the `checkpoints`
+    /// minutes.
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn get_match_data_collection(
         &self,
         ids: Vec<String>,
+        puuid: &str,
+        checkpoints: &[u32],
+    ) -> MatchBatch {
+        let pool = ThreadPool::new(self.workers.clamp(1, ids.len().max(1)));
+        let (tx, rx) = mpsc::channel();
+
+        for (index, id) in ids.into_iter().enumerate() {
+            let tx = tx.clone();
+            let inter = self.clone();
+            let puuid = puuid.to_owned();
+            let checkpoints = checkpoints.to_vec();
+            pool.execute(move || {
+                let result = pollster::block_on(inter.fetch_raw_data(&id, &puuid, &checkpoints));
+                // The receiver only goes away if the caller has, so there is nobody to tell.
+                let _: Result<(), _> = tx.send((index, id, result));
+            });
+        }
+        drop(tx);
+
+        let mut results: Vec<_> = rx.iter().collect();
+        results.sort_by_key(|(index, _, _)| *index);
+        MatchBatch::collect(results.into_iter().map(|(_, id, result)| (id, result)))
+    }
+
+    /// [`Interface::get_match_data_collection`] as a future. In the browser up to `workers`
+    /// matches are in flight at once on the event loop. Natively this blocks: the batch goes to
+    /// the worker pool and the calling thread waits for all of it; see [`Interface`].
+    pub async fn get_match_data_collection_async(
+        &self,
+        ids: Vec<String>,
+        puuid: &str,
+        checkpoints: &[u32],
+    ) -> MatchBatch {
+        #[cfg(not(target_arch = "wasm32"))]
+        return self.get_match_data_collection(ids, puuid, checkpoints);
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            let puuid = puuid.to_owned();
+            let mut results = Vec::new();
+            for chunk in ids.chunks(self.workers.max(1)) {
+                let fetches = chunk
+                    .iter()
+                    .map(|id| self.fetch_raw_data(id, &puuid, checkpoints));
+                let chunk_results = futures_util::future::join_all(fetches).await;
+                results.extend(chunk.iter().cloned().zip(chunk_results));
+            }
+            MatchBatch::collect(results)
+        }
+    }
+
+    /// Downloads one match and its timeline and boils them down to a [`RawData`]. `None` if the
+    /// match isn't one we analyze.
+    async fn fetch_raw_data(
+        &self,
+        id: &String,
         puuid: &String,
-    ) -> Result<Vec<RawData>, ApiError> {
-        let mut out: Vec<RawData> = Vec::new();
+        checkpoints: &[u32],
+    ) -> Result<Option<RawData>, ApiError> {
         let check_valid_game = |game: &MatchData| -> bool {
             if game.info.end_of_game_result != "GameComplete"
-                || game.info.game_mode != "CLASSIC"
+                || !QueueType::from_id(game.info.queue_id).accepts_mode(&game.info.game_mode)
                 || game.info.game_type != "MATCHED_GAME"
             {
                 return false;
@@ -50,43 +265,41 @@ impl Interface {
             true
         };
 
-        for id in &ids {
-            let game_data = self.request_game_data(id)?;
-            if check_valid_game(&game_data) {
-                let game_tl = self.request_match_timeline(id)?;
-                let mut raw = RawData::new(&game_data, &game_tl);
-                raw.find_me(puuid);
-                //println!(
-                //    "User Inventory @ 15: {:?}",
-                //    RawData::find_player_inventory(&game_tl, 2)
-                //);
-                out.push(raw);
-            }
+        let game_data = self.request_game_data(id).await?;
+        if !check_valid_game(&game_data) {
+            return Ok(None);
+        }
+        let game_tl = self.request_match_timeline(id).await?;
+        if !RawData::can_analyze(&game_tl, checkpoints) {
+            return Ok(None);
         }
-        Ok(out)
+        Ok(Some(RawData::for_player(
+            &game_data,
+            &game_tl,
+            checkpoints,
+            puuid,
+        )))
     }
 
-    fn request_game_data(&self, id: &String) -> Result<MatchData, ApiError> {
-        let resp = reqwest::blocking::get(format!(
-            "https://{}.api.riotgames.com/lol/match/v5/matches/{}?api_key={}",
-            Self::get_server(&self.server),
+    async fn request_game_data(&self, id: &String) -> Result<MatchData, ApiError> {
+        self.fetch_cached(
+            CacheKind::Match,
             id,
-            self.api_key
-        ))?
-        .text()?;
-        let game: MatchData = serde_json::from_str(&resp)?;
-        Ok(game)
+            self.server.regional(),
+            "match-v5.getMatch",
+            &format!("/lol/match/v5/matches/{id}"),
+        )
+        .await
     }
 
-    fn request_match_timeline(&self, id: &String) -> Result<Timeline, ApiError> {
-        let resp = reqwest::blocking::get(format!(
-            "https://{}.api.riotgames.com/lol/match/v5/matches/{}/timeline?api_key={}",
-            Self::get_server(&self.server),
+    async fn request_match_timeline(&self, id: &String) -> Result<Timeline, ApiError> {
+        self.fetch_cached(
+            CacheKind::Timeline,
             id,
-            self.api_key
-        ))?
-        .text()?;
-        let out: Timeline = serde_json::from_str(&resp)?;
-        Ok(out)
+            self.server.regional(),
+            "match-v5.getTimeline",
+            &format!("/lol/match/v5/matches/{id}/timeline"),
+        )
+        .await
     }
 }
@@ -1,23 +1,29 @@
 use serde_derive::Deserialize;
 use serde_derive::Serialize;
 
+use crate::interface::schema::Extra;
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct MatchData {
     pub metadata: Metadata,
     pub info: Info,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Metadata {
     pub data_version: String,
     pub match_id: String,
     pub participants: Vec<String>,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Info {
     pub end_of_game_result: String,
     pub game_creation: i64,
@@ -35,10 +41,12 @@ pub struct Info {
     pub queue_id: i64,
     pub teams: Vec<Team>,
     pub tournament_code: String,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Participant {
     #[serde(rename = "PlayerScore0")]
     pub player_score0: i64,
@@ -208,10 +216,12 @@ pub struct Participant {
     pub wards_killed: i64,
     pub wards_placed: i64,
     pub win: bool,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Challenges {
     #[serde(rename = "12AssistStreakCount")]
     pub n12assist_streak_count: i64,
@@ -369,10 +379,12 @@ pub struct Challenges {
     pub highest_crowd_control_score: Option<i64>,
     pub faster_support_quest_completion: Option<i64>,
     pub highest_ward_kills: Option<i64>,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Missions {
     pub player_score0: i64,
     pub player_score1: i64,
@@ -386,59 +398,73 @@ pub struct Missions {
     pub player_score9: i64,
     pub player_score10: i64,
     pub player_score11: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Perks {
     pub stat_perks: StatPerks,
     pub styles: Vec<Style>,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct StatPerks {
     pub defense: i64,
     pub flex: i64,
     pub offense: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Style {
     pub description: String,
     pub selections: Vec<Selection>,
     pub style: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Selection {
     pub perk: i64,
     pub var1: i64,
     pub var2: i64,
     pub var3: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Team {
     pub bans: Vec<Ban>,
     pub feats: Feats,
     pub objectives: Objectives,
     pub team_id: i64,
     pub win: bool,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Ban {
     pub champion_id: i64,
     pub pick_turn: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Feats {
     #[serde(rename = "EPIC_MONSTER_KILL")]
     pub epic_monster_kill: EpicMonsterKill,
@@ -446,28 +472,36 @@ pub struct Feats {
     pub first_blood: FirstBlood,
     #[serde(rename = "FIRST_TURRET")]
     pub first_turret: FirstTurret,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct EpicMonsterKill {
     pub feat_state: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct FirstBlood {
     pub feat_state: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct FirstTurret {
     pub feat_state: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Objectives {
     pub atakhan: Atakhan,
     pub baron: Baron,
@@ -477,60 +511,78 @@ pub struct Objectives {
     pub inhibitor: Inhibitor,
     pub rift_herald: RiftHerald,
     pub tower: Tower,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Atakhan {
     pub first: bool,
     pub kills: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Baron {
     pub first: bool,
     pub kills: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Champion {
     pub first: bool,
     pub kills: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Dragon {
     pub first: bool,
     pub kills: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Horde {
     pub first: bool,
     pub kills: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Inhibitor {
     pub first: bool,
     pub kills: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct RiftHerald {
     pub first: bool,
     pub kills: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Tower {
     pub first: bool,
     pub kills: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
@@ -1,6 +1,10 @@
 use serde_derive::Deserialize;
 use serde_derive::Serialize;
 
+use crate::api_error::ApiError;
+use crate::interface::Interface;
+use crate::queue::QueueType;
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RankedData {
@@ -8,7 +12,11 @@ pub struct RankedData {
     pub queue_type: String,
     pub tier: String,
     pub rank: String,
+    /// Dropped from league-v4 responses in favour of the PUUID; kept for older saves.
+    #[serde(default)]
     pub summoner_id: String,
+    #[serde(default)]
+    pub puuid: String,
     pub league_points: i64,
     pub wins: i64,
     pub losses: i64,
@@ -17,3 +25,94 @@ pub struct RankedData {
     pub fresh_blood: bool,
     pub hot_streak: bool,
 }
+
+impl RankedData {
+    /// Which of our queues this entry is for. Only solo and flex have ladders.
+    pub fn queue(&self) -> Option<QueueType> {
+        match self.queue_type.as_str() {
+            "RANKED_SOLO_5x5" => Some(QueueType::RankedSolo),
+            "RANKED_FLEX_SR" => Some(QueueType::RankedFlex),
+            _ => None,
+        }
+    }
+
+    /// Rank flattened onto a single LP scale: 400 per tier, 100 per division, plus LP. Master and
+    /// above have no divisions and share one tier, so their LP just keeps counting up.
+    pub fn total_lp(&self) -> i64 {
+        let tier = match self.tier.as_str() {
+            "IRON" => 0,
+            "BRONZE" => 1,
+            "SILVER" => 2,
+            "GOLD" => 3,
+            "PLATINUM" => 4,
+            "EMERALD" => 5,
+            "DIAMOND" => 6,
+            _ => 7,
+        };
+        let division = match self.rank.as_str() {
+            "IV" => 0,
+            "III" => 1,
+            "II" => 2,
+            "I" if tier < 7 => 3,
+            _ => 0,
+        };
+        tier * 400 + division * 100 + self.league_points
+    }
+
+    /// e.g. ``GOLD II 54 LP``, or ``MASTER 212 LP``.
+    pub fn rank_string(&self) -> String {
+        if self.total_lp() >= 7 * 400 {
+            format!("{} {} LP", self.tier, self.league_points)
+        } else {
+            format!("{} {} {} LP", self.tier, self.rank, self.league_points)
+        }
+    }
+
+    pub fn win_rate(&self) -> f32 {
+        let games = self.wins + self.losses;
+        if games == 0 {
+            return 0.0;
+        }
+        self.wins as f32 / games as f32 * 100.0
+    }
+}
+
+/// A player's ladder standing in one queue at one point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RankSnapshot {
+    /// Unix timestamp (milliseconds) the entry was fetched at.
+    pub taken_at: i64,
+    pub queue: QueueType,
+    pub entry: RankedData,
+}
+
+impl RankSnapshot {
+    /// Whether anything that would show up on the ladder changed between the two.
+    pub fn same_standing(&self, entry: &RankedData) -> bool {
+        self.entry.tier == entry.tier
+            && self.entry.rank == entry.rank
+            && self.entry.league_points == entry.league_points
+            && self.entry.wins == entry.wins
+            && self.entry.losses == entry.losses
+    }
+}
+
+impl Interface {
+    /// Every league entry the player has this season. Unranked queues are simply missing.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn request_ranked_data(&self, puuid: &str) -> Result<Vec<RankedData>, ApiError> {
+        pollster::block_on(self.request_ranked_data_async(puuid))
+    }
+
+    pub async fn request_ranked_data_async(
+        &self,
+        puuid: &str,
+    ) -> Result<Vec<RankedData>, ApiError> {
+        self.fetch_json(
+            self.server.platform(),
+            "league-v4.getLeagueEntriesByPUUID",
+            &format!("/lol/league/v4/entries/by-puuid/{puuid}"),
+        )
+        .await
+    }
+}
@@ -0,0 +1,436 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+// std's Instant panics in the browser; this one is std's everywhere else.
+use web_time::Instant;
+
+use crate::interface::transport::Response;
+
+/// One "N requests every W seconds" window, as Riot spells it in ``X-App-Rate-Limit``.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limit {
+    pub requests: u32,
+    pub window: Duration,
+}
+
+impl Limit {
+    /// Parses Riot's ``20:1,100:120`` format into `(count, seconds)` pairs.
+    pub fn parse_header(value: &str) -> Vec<(u32, u64)> {
+        value
+            .split(',')
+            .filter_map(|pair| {
+                let (count, secs) = pair.trim().split_once(':')?;
+                Some((count.parse().ok()?, secs.parse().ok()?))
+            })
+            .collect()
+    }
+}
+
+/// Which limit a [`QuotaUsage`] line is describing.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Scope {
+    App,
+    Method(String),
+}
+
+/// How much of a single window has been spent, for display.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaUsage {
+    pub host: String,
+    pub scope: Scope,
+    pub used: u32,
+    pub limit: u32,
+    pub window: Duration,
+}
+
+impl QuotaUsage {
+    pub fn remaining(&self) -> u32 {
+        self.limit.saturating_sub(self.used)
+    }
+}
+
+/// Bookkeeping for one app or method limit on one routing host.
+#[derive(Debug, Default)]
+struct Bucket {
+    limits: Vec<Limit>,
+    /// When each request we sent through this bucket went out, oldest first.
+    sent: VecDeque<Instant>,
+    /// What Riot last told us the counts were, keyed by window length.
+    reported: HashMap<Duration, u32>,
+    blocked_until: Option<Instant>,
+}
+
+impl Bucket {
+    fn with_limits(limits: Vec<Limit>) -> Self {
+        Self {
+            limits,
+            ..Default::default()
+        }
+    }
+
+    fn used(&self, limit: &Limit, now: Instant) -> u32 {
+        let local = self
+            .sent
+            .iter()
+            .filter(|t| now.duration_since(**t) < limit.window)
+            .count() as u32;
+        local.max(self.reported.get(&limit.window).copied().unwrap_or(0))
+    }
+
+    /// How long until a request may go out through this bucket.
+    fn wait(&self, now: Instant) -> Duration {
+        let mut wait = self
+            .blocked_until
+            .map(|t| t.saturating_duration_since(now))
+            .unwrap_or_default();
+        for limit in &self.limits {
+            if self.used(limit, now) < limit.requests {
+                continue;
+            }
+            // Full window; the slot frees up once the oldest request in it ages out.
+            let in_window: Vec<&Instant> = self
+                .sent
+                .iter()
+                .filter(|t| now.duration_since(**t) < limit.window)
+                .collect();
+            let free_at = in_window
+                .len()
+                .checked_sub(limit.requests as usize)
+                .and_then(|i| in_window.get(i))
+                .map(|t| **t + limit.window)
+                .unwrap_or(now + limit.window);
+            wait = wait.max(free_at.saturating_duration_since(now));
+        }
+        wait
+    }
+
+    fn record_sent(&mut self, now: Instant) {
+        self.sent.push_back(now);
+        let longest = self
+            .limits
+            .iter()
+            .map(|l| l.window)
+            .max()
+            .unwrap_or_default();
+        while let Some(t) = self.sent.front() {
+            if now.duration_since(*t) < longest {
+                break;
+            }
+            self.sent.pop_front();
+        }
+        // Our own counts are fresher than anything Riot told us before this request.
+        self.reported.clear();
+    }
+
+    fn update(&mut self, limits: Option<&str>, counts: Option<&str>) {
+        if let Some(limits) = limits {
+            let parsed: Vec<Limit> = Limit::parse_header(limits)
+                .into_iter()
+                .map(|(requests, secs)| Limit {
+                    requests,
+                    window: Duration::from_secs(secs),
+                })
+                .collect();
+            if !parsed.is_empty() {
+                self.limits = parsed;
+            }
+        }
+        if let Some(counts) = counts {
+            self.reported = Limit::parse_header(counts)
+                .into_iter()
+                .map(|(count, secs)| (Duration::from_secs(secs), count))
+                .collect();
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct HostState {
+    app: Bucket,
+    methods: HashMap<String, Bucket>,
+}
+
+/// Keeps requests under Riot's rate limits. Limits are tracked per routing host (``americas``,
+/// ``na1``, ...) for the app as a whole and for each method, learned from the ``X-*-Rate-Limit``
+/// headers on every response. Until the first response comes back the personal dev key limits
+/// are assumed.
+#[derive(Debug)]
+pub struct RateLimiter {
+    hosts: Mutex<HashMap<String, HostState>>,
+    default_limits: Vec<Limit>,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(vec![
+            Limit {
+                requests: 20,
+                window: Duration::from_secs(1),
+            },
+            Limit {
+                requests: 100,
+                window: Duration::from_secs(120),
+            },
+        ])
+    }
+}
+
+impl RateLimiter {
+    pub fn new(default_limits: Vec<Limit>) -> Self {
+        Self {
+            hosts: Mutex::new(HashMap::new()),
+            default_limits,
+        }
+    }
+
+    /// The limiter every [`Interface`](crate::interface::Interface) uses unless told otherwise.
+    /// Riot counts requests per key, so everything in the process should share one.
+    pub fn shared() -> Arc<Self> {
+        static SHARED: OnceLock<Arc<RateLimiter>> = OnceLock::new();
+        Arc::clone(SHARED.get_or_init(|| Arc::new(Self::default())))
+    }
+
+    /// Blocks until a request to `method` on `host` fits inside every known limit, then counts it
+    /// as sent.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn acquire(&self, host: &str, method: &str) {
+        loop {
+            match self.reserve(host, method) {
+                Some(wait) => std::thread::sleep(wait),
+                None => return,
+            }
+        }
+    }
+
+    /// [`RateLimiter::acquire`] as a future. In the browser the wait yields to the event loop;
+    /// natively it still sleeps the thread it's polled on.
+    pub async fn acquire_async(&self, host: &str, method: &str) {
+        while let Some(wait) = self.reserve(host, method) {
+            sleep(wait).await;
+        }
+    }
+
+    /// Counts a request as sent if it fits right now, otherwise says how long to wait before
+    /// asking again.
+    fn reserve(&self, host: &str, method: &str) -> Option<Duration> {
+        let mut hosts = self.lock();
+        let state = self.host_state(&mut hosts, host);
+        let now = Instant::now();
+        let method_bucket = state.methods.entry(method.to_owned()).or_default();
+        let wait = state.app.wait(now).max(method_bucket.wait(now));
+        if !wait.is_zero() {
+            return Some(wait);
+        }
+        method_bucket.record_sent(now);
+        state.app.record_sent(now);
+        None
+    }
+
+    /// Feeds the rate limit headers of a response back in. On a 429 the offending bucket is held
+    /// shut for ``Retry-After`` seconds (one second if Riot didn't say).
+    pub fn record(&self, host: &str, method: &str, resp: &Response) {
+        let mut hosts = self.lock();
+        let state = self.host_state(&mut hosts, host);
+        state.app.update(
+            resp.header("x-app-rate-limit"),
+            resp.header("x-app-rate-limit-count"),
+        );
+        let method_bucket = state.methods.entry(method.to_owned()).or_default();
+        method_bucket.update(
+            resp.header("x-method-rate-limit"),
+            resp.header("x-method-rate-limit-count"),
+        );
+
+        if resp.status == 429 {
+            let retry_after = resp
+                .header("retry-after")
+                .and_then(|v| v.trim().parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(Duration::from_secs(1));
+            let until = Some(Instant::now() + retry_after);
+            match resp.header("x-rate-limit-type") {
+                Some("method") => method_bucket.blocked_until = until,
+                _ => state.app.blocked_until = until,
+            }
+        }
+    }
+
+    /// Current usage of every window we know about, sorted by host then scope.
+    pub fn usage(&self) -> Vec<QuotaUsage> {
+        let hosts = self.lock();
+        let now = Instant::now();
+        let mut out = Vec::new();
+        for (host, state) in hosts.iter() {
+            let buckets = std::iter::once((Scope::App, &state.app)).chain(
+                state
+                    .methods
+                    .iter()
+                    .map(|(m, b)| (Scope::Method(m.clone()), b)),
+            );
+            for (scope, bucket) in buckets {
+                for limit in &bucket.limits {
+                    out.push(QuotaUsage {
+                        host: host.clone(),
+                        scope: scope.clone(),
+                        used: bucket.used(limit, now),
+                        limit: limit.requests,
+                        window: limit.window,
+                    });
+                }
+            }
+        }
+        out.sort_by(|a, b| (&a.host, &a.scope, a.window).cmp(&(&b.host, &b.scope, b.window)));
+        out
+    }
+
+    fn host_state<'a>(
+        &self,
+        hosts: &'a mut HashMap<String, HostState>,
+        host: &str,
+    ) -> &'a mut HostState {
+        hosts.entry(host.to_owned()).or_insert_with(|| HostState {
+            app: Bucket::with_limits(self.default_limits.clone()),
+            methods: HashMap::new(),
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, HostState>> {
+        // A panic mid-update leaves nothing worse than slightly stale counts.
+        self.hosts
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Waits out `wait` without tying up the browser's event loop. Natively it blocks, sleeping the
+/// thread the future is polled on, which is only fine because native callers poll on a thread
+/// of their own; see [`Interface`](crate::interface::Interface).
+pub(crate) async fn sleep(wait: Duration) {
+    #[cfg(target_arch = "wasm32")]
+    gloo_timers::future::sleep(wait).await;
+    #[cfg(not(target_arch = "wasm32"))]
+    std::thread::sleep(wait);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOST: &str = "na1";
+    const METHOD: &str = "summoner-v4.getByPUUID";
+
+    fn response(status: u16, headers: &[(&str, &str)]) -> Response {
+        Response {
+            status,
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_ascii_lowercase(), (*value).to_owned()))
+                .collect(),
+            body: String::new(),
+        }
+    }
+
+    fn limit(requests: u32, secs: u64) -> Limit {
+        Limit {
+            requests,
+            window: Duration::from_secs(secs),
+        }
+    }
+
+    /// `(scope, used, limit, window in seconds)` for every window on [`HOST`].
+    fn usage(limiter: &RateLimiter) -> Vec<(Scope, u32, u32, u64)> {
+        limiter
+            .usage()
+            .into_iter()
+            .filter(|u| u.host == HOST)
+            .map(|u| (u.scope, u.used, u.limit, u.window.as_secs()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_limit_headers() {
+        let cases: [(&str, &[(u32, u64)]); 4] = [
+            ("20:1,100:120", &[(20, 1), (100, 120)]),
+            (" 500:10 ", &[(500, 10)]),
+            ("20:1,junk,5:x", &[(20, 1)]),
+            ("", &[]),
+        ];
+        for (header, expected) in cases {
+            assert_eq!(Limit::parse_header(header), expected, "{header:?}");
+        }
+    }
+
+    #[test]
+    fn learns_limits_and_counts_from_headers() {
+        let limiter = RateLimiter::new(vec![limit(20, 1)]);
+        limiter.record(
+            HOST,
+            METHOD,
+            &response(
+                200,
+                &[
+                    ("X-App-Rate-Limit", "20:1,100:120"),
+                    ("X-App-Rate-Limit-Count", "3:1,40:120"),
+                    ("X-Method-Rate-Limit", "500:10"),
+                    ("X-Method-Rate-Limit-Count", "7:10"),
+                ],
+            ),
+        );
+        assert_eq!(
+            usage(&limiter),
+            [
+                (Scope::App, 3, 20, 1),
+                (Scope::App, 40, 100, 120),
+                (Scope::Method(METHOD.to_owned()), 7, 500, 10),
+            ]
+        );
+    }
+
+    #[test]
+    fn holds_requests_once_a_window_is_full() {
+        let limiter = RateLimiter::new(vec![limit(2, 10)]);
+        assert_eq!(limiter.reserve(HOST, METHOD), None);
+        assert_eq!(limiter.reserve(HOST, "other-method"), None);
+        let wait = limiter
+            .reserve(HOST, METHOD)
+            .expect("the app window is full");
+        assert!(wait > Duration::from_secs(9) && wait <= Duration::from_secs(10));
+        // Each routing host has limits of its own.
+        assert_eq!(limiter.reserve("euw1", METHOD), None);
+    }
+
+    #[test]
+    fn holds_requests_when_riot_counts_a_full_window() {
+        let limiter = RateLimiter::new(vec![limit(20, 1)]);
+        limiter.record(
+            HOST,
+            METHOD,
+            &response(200, &[("X-App-Rate-Limit-Count", "20:1")]),
+        );
+        assert!(limiter.reserve(HOST, METHOD).is_some());
+    }
+
+    #[test]
+    fn waits_out_retry_after_for_the_bucket_that_was_hit() {
+        let limiter = RateLimiter::new(vec![limit(20, 1)]);
+        limiter.record(
+            HOST,
+            METHOD,
+            &response(
+                429,
+                &[("Retry-After", "7"), ("X-Rate-Limit-Type", "method")],
+            ),
+        );
+        let wait = limiter.reserve(HOST, METHOD).expect("the method is held");
+        assert!(wait > Duration::from_secs(6) && wait <= Duration::from_secs(7));
+        assert_eq!(limiter.reserve(HOST, "other-method"), None);
+
+        // Without a type it's the whole app, and without Retry-After a second.
+        limiter.record(HOST, "other-method", &response(429, &[]));
+        let wait = limiter
+            .reserve(HOST, "third-method")
+            .expect("the app is held");
+        assert!(wait > Duration::ZERO && wait <= Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Fields Riot sent that a model has no place for yet. They're kept so nothing is lost, but left
+/// out when the model is serialized again, so a round trip shows exactly what the model
+/// understood; see [`SchemaDrift`].
+pub type Extra = Map<String, Value>;
+
+/// How a response differed from the model it was read into: fields that weren't there and fell
+/// back to their defaults, and fields the model doesn't know. Paths are dotted, with ``[]``
+/// standing in for every element of an array, e.g.
+/// ``info.participants[].challenges.swarmDefeatAatrox``.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaDrift {
+    pub missing: BTreeSet<String>,
+    pub unknown: BTreeSet<String>,
+}
+
+impl SchemaDrift {
+    /// Compares a response as it arrived with the model it was parsed into.
+    pub fn check<T: Serialize>(body: &Value, model: &T) -> Self {
+        let mut drift = Self::default();
+        if let Ok(modeled) = serde_json::to_value(model) {
+            drift.walk(body, &modeled, "");
+        }
+        drift
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.unknown.is_empty()
+    }
+
+    /// Adds `other` in, keeping only what wasn't already known. The result is what's new.
+    pub fn absorb(&mut self, other: SchemaDrift) -> SchemaDrift {
+        let new = SchemaDrift {
+            missing: other.missing.difference(&self.missing).cloned().collect(),
+            unknown: other.unknown.difference(&self.unknown).cloned().collect(),
+        };
+        self.missing.extend(new.missing.iter().cloned());
+        self.unknown.extend(new.unknown.iter().cloned());
+        new
+    }
+
+    fn walk(&mut self, body: &Value, modeled: &Value, path: &str) {
+        match (body, modeled) {
+            (Value::Object(body), Value::Object(modeled)) => {
+                for key in body.keys().filter(|k| !modeled.contains_key(*k)) {
+                    self.unknown.insert(join(path, key));
+                }
+                for (key, value) in modeled {
+                    match body.get(key) {
+                        Some(sent) => self.walk(sent, value, &join(path, key)),
+                        // Optional fields and lists that simply had nothing in them are left
+                        // out by Riot as a matter of course.
+                        None if !is_blank(value) => {
+                            self.missing.insert(join(path, key));
+                        }
+                        None => {}
+                    }
+                }
+            }
+            (Value::Array(body), Value::Array(modeled)) => {
+                let path = format!("{path}[]");
+                for (sent, value) in body.iter().zip(modeled) {
+                    self.walk(sent, value, &path);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl fmt::Display for SchemaDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |paths: &BTreeSet<String>| paths.iter().cloned().collect::<Vec<_>>().join(", ");
+        match (self.missing.is_empty(), self.unknown.is_empty()) {
+            (true, true) => write!(f, "no drift"),
+            (false, true) => write!(f, "missing {}", list(&self.missing)),
+            (true, false) => write!(f, "new {}", list(&self.unknown)),
+            (false, false) => write!(
+                f,
+                "missing {}; new {}",
+                list(&self.missing),
+                list(&self.unknown)
+            ),
+        }
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    match path {
+        "" => key.to_owned(),
+        _ => format!("{path}.{key}"),
+    }
+}
+
+fn is_blank(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
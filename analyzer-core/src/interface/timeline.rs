@@ -1,382 +1,415 @@
+use std::collections::BTreeMap;
+
 use serde_derive::Deserialize;
 use serde_derive::Serialize;
 
+use crate::interface::schema::Extra;
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Timeline {
     pub metadata: Metadata,
     pub info: Info,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Metadata {
     pub data_version: String,
     pub match_id: String,
     pub participants: Vec<String>,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Info {
     pub end_of_game_result: String,
     pub frame_interval: i64,
     pub frames: Vec<Frame>,
     pub game_id: i64,
     pub participants: Vec<Participant>,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Frame {
     pub events: Vec<Event>,
     pub participant_frames: ParticipantFrames,
     pub timestamp: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Event {
-    pub creator_id: Option<i64>,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
+}
+
+/// Something that happened during a frame, told apart by Riot's ``type`` field. Each kind
+/// carries the fields Riot always sends with it; kinds the models don't cover yet are kept as
+/// [`Event::Unknown`] rather than failing the whole timeline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Event {
+    ChampionKill(ChampionKill),
+    ChampionSpecialKill(ChampionSpecialKill),
+    ItemPurchased(ItemTransaction),
+    ItemSold(ItemTransaction),
+    ItemDestroyed(ItemTransaction),
+    ItemUndo(ItemUndo),
+    WardPlaced(WardPlaced),
+    WardKill(WardKill),
+    EliteMonsterKill(EliteMonsterKill),
+    BuildingKill(BuildingKill),
+    TurretPlateDestroyed(TurretPlateDestroyed),
+    SkillLevelUp(SkillLevelUp),
+    LevelUp(LevelUp),
+    DragonSoulGiven(DragonSoulGiven),
+    GameEnd(GameEnd),
+    #[serde(untagged)]
+    Unknown(UnknownEvent),
+}
+
+impl Event {
+    /// Milliseconds since the game started.
+    pub fn timestamp(&self) -> i64 {
+        match self {
+            Event::ChampionKill(e) => e.timestamp,
+            Event::ChampionSpecialKill(e) => e.timestamp,
+            Event::ItemPurchased(e) | Event::ItemSold(e) | Event::ItemDestroyed(e) => e.timestamp,
+            Event::ItemUndo(e) => e.timestamp,
+            Event::WardPlaced(e) => e.timestamp,
+            Event::WardKill(e) => e.timestamp,
+            Event::EliteMonsterKill(e) => e.timestamp,
+            Event::BuildingKill(e) => e.timestamp,
+            Event::TurretPlateDestroyed(e) => e.timestamp,
+            Event::SkillLevelUp(e) => e.timestamp,
+            Event::LevelUp(e) => e.timestamp,
+            Event::DragonSoulGiven(e) => e.timestamp,
+            Event::GameEnd(e) => e.timestamp,
+            Event::Unknown(e) => e.timestamp,
+        }
+    }
+
+    /// Riot's name for this kind of event, e.g. ``ITEM_PURCHASED``.
+    pub fn kind(&self) -> &str {
+        match self {
+            Event::ChampionKill(_) => "CHAMPION_KILL",
+            Event::ChampionSpecialKill(_) => "CHAMPION_SPECIAL_KILL",
+            Event::ItemPurchased(_) => "ITEM_PURCHASED",
+            Event::ItemSold(_) => "ITEM_SOLD",
+            Event::ItemDestroyed(_) => "ITEM_DESTROYED",
+            Event::ItemUndo(_) => "ITEM_UNDO",
+            Event::WardPlaced(_) => "WARD_PLACED",
+            Event::WardKill(_) => "WARD_KILL",
+            Event::EliteMonsterKill(_) => "ELITE_MONSTER_KILL",
+            Event::BuildingKill(_) => "BUILDING_KILL",
+            Event::TurretPlateDestroyed(_) => "TURRET_PLATE_DESTROYED",
+            Event::SkillLevelUp(_) => "SKILL_LEVEL_UP",
+            Event::LevelUp(_) => "LEVEL_UP",
+            Event::DragonSoulGiven(_) => "DRAGON_SOUL_GIVEN",
+            Event::GameEnd(_) => "GAME_END",
+            Event::Unknown(e) => &e.type_field,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChampionKill {
     pub timestamp: i64,
-    #[serde(rename = "type")]
-    pub type_field: String,
-    pub ward_type: Option<String>,
-    pub item_id: Option<i64>,
-    pub participant_id: Option<i64>,
-    pub level_up_type: Option<String>,
-    pub skill_slot: Option<i64>,
-    pub real_timestamp: Option<i64>,
-    pub level: Option<i64>,
-    pub after_id: Option<i64>,
-    pub before_id: Option<i64>,
-    pub gold_gain: Option<i64>,
-    #[serde(default)]
+    /// 0 when the victim was executed by a turret, minion or monster.
+    pub killer_id: i64,
+    pub victim_id: i64,
     pub assisting_participant_ids: Vec<i64>,
-    pub bounty: Option<i64>,
-    pub kill_streak_length: Option<i64>,
-    pub killer_id: Option<i64>,
-    pub position: Option<Position>,
-    pub shutdown_bounty: Option<i64>,
-    #[serde(default)]
+    pub bounty: i64,
+    pub shutdown_bounty: i64,
+    pub kill_streak_length: i64,
+    pub position: Position,
     pub victim_damage_dealt: Vec<VictimDamageDealt>,
-    #[serde(default)]
     pub victim_damage_received: Vec<VictimDamageReceived>,
-    pub victim_id: Option<i64>,
-    pub kill_type: Option<String>,
-    pub lane_type: Option<String>,
-    pub team_id: Option<i64>,
-    pub killer_team_id: Option<i64>,
-    pub monster_sub_type: Option<String>,
-    pub monster_type: Option<String>,
-    pub name: Option<String>,
-    pub building_type: Option<String>,
-    pub tower_type: Option<String>,
-    pub actual_start_time: Option<i64>,
-    pub multi_kill_length: Option<i64>,
-    pub game_id: Option<i64>,
-    pub winning_team: Option<i64>,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
+/// First blood, multikills and the like, sent alongside the [`ChampionKill`] they're about.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Position {
-    pub x: i64,
-    pub y: i64,
+#[serde(rename_all = "camelCase", default)]
+pub struct ChampionSpecialKill {
+    pub timestamp: i64,
+    pub killer_id: i64,
+    /// ``KILL_FIRST_BLOOD``, ``KILL_MULTI`` or ``KILL_ACE``.
+    pub kill_type: String,
+    pub multi_kill_length: Option<i64>,
+    pub position: Position,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
+/// An item bought, sold or used up.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct VictimDamageDealt {
-    pub basic: bool,
-    pub magic_damage: i64,
-    pub name: String,
+#[serde(rename_all = "camelCase", default)]
+pub struct ItemTransaction {
+    pub timestamp: i64,
     pub participant_id: i64,
-    pub physical_damage: i64,
-    pub spell_name: String,
-    pub spell_slot: i64,
-    pub true_damage: i64,
-    #[serde(rename = "type")]
-    pub type_field: String,
+    pub item_id: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
+/// An undone purchase or sale: the item in the slot before and after the undo, 0 for none.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct VictimDamageReceived {
-    pub basic: bool,
-    pub magic_damage: i64,
-    pub name: String,
+#[serde(rename_all = "camelCase", default)]
+pub struct ItemUndo {
+    pub timestamp: i64,
     pub participant_id: i64,
-    pub physical_damage: i64,
-    pub spell_name: String,
-    pub spell_slot: i64,
-    pub true_damage: i64,
-    #[serde(rename = "type")]
-    pub type_field: String,
+    pub before_id: i64,
+    pub after_id: i64,
+    pub gold_gain: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ParticipantFrames {
-    #[serde(rename = "1")]
-    pub n1: n1,
-    #[serde(rename = "2")]
-    pub n2: n2,
-    #[serde(rename = "3")]
-    pub n3: n3,
-    #[serde(rename = "4")]
-    pub n4: n4,
-    #[serde(rename = "5")]
-    pub n5: n5,
-    #[serde(rename = "6")]
-    pub n6: n6,
-    #[serde(rename = "7")]
-    pub n7: n7,
-    #[serde(rename = "8")]
-    pub n8: n8,
-    #[serde(rename = "9")]
-    pub n9: n9,
-    #[serde(rename = "10")]
-    pub n10: n10,
+#[serde(rename_all = "camelCase", default)]
+pub struct WardPlaced {
+    pub timestamp: i64,
+    pub creator_id: i64,
+    pub ward_type: String,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-#[allow(non_camel_case_types)]
-pub struct n1 {
-    pub champion_stats: ChampionStats,
-    pub current_gold: i64,
-    pub damage_stats: DamageStats,
-    pub gold_per_second: i64,
-    pub jungle_minions_killed: i64,
-    pub level: i64,
-    pub minions_killed: i64,
-    pub participant_id: i64,
-    pub position: Position2,
-    pub time_enemy_spent_controlled: i64,
-    pub total_gold: i64,
-    pub xp: i64,
+#[serde(rename_all = "camelCase", default)]
+pub struct WardKill {
+    pub timestamp: i64,
+    pub killer_id: i64,
+    pub ward_type: String,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
+/// Dragons, barons, heralds, grubs and the like.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ChampionStats {
-    pub ability_haste: i64,
-    pub ability_power: i64,
-    pub armor: i64,
-    pub armor_pen: i64,
-    pub armor_pen_percent: i64,
-    pub attack_damage: i64,
-    pub attack_speed: i64,
-    pub bonus_armor_pen_percent: i64,
-    pub bonus_magic_pen_percent: i64,
-    pub cc_reduction: i64,
-    pub cooldown_reduction: i64,
-    pub health: i64,
-    pub health_max: i64,
-    pub health_regen: i64,
-    pub lifesteal: i64,
-    pub magic_pen: i64,
-    pub magic_pen_percent: i64,
-    pub magic_resist: i64,
-    pub movement_speed: i64,
-    pub omnivamp: i64,
-    pub physical_vamp: i64,
-    pub power: i64,
-    pub power_max: i64,
-    pub power_regen: i64,
-    pub spell_vamp: i64,
+#[serde(rename_all = "camelCase", default)]
+pub struct EliteMonsterKill {
+    pub timestamp: i64,
+    pub killer_id: i64,
+    pub killer_team_id: i64,
+    pub assisting_participant_ids: Vec<i64>,
+    pub bounty: i64,
+    pub monster_type: String,
+    pub monster_sub_type: Option<String>,
+    pub position: Position,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
+/// A turret or inhibitor destroyed. `team_id` is the team that lost it.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DamageStats {
-    pub magic_damage_done: i64,
-    pub magic_damage_done_to_champions: i64,
-    pub magic_damage_taken: i64,
-    pub physical_damage_done: i64,
-    pub physical_damage_done_to_champions: i64,
-    pub physical_damage_taken: i64,
-    pub total_damage_done: i64,
-    pub total_damage_done_to_champions: i64,
-    pub total_damage_taken: i64,
-    pub true_damage_done: i64,
-    pub true_damage_done_to_champions: i64,
-    pub true_damage_taken: i64,
+#[serde(rename_all = "camelCase", default)]
+pub struct BuildingKill {
+    pub timestamp: i64,
+    pub killer_id: i64,
+    pub team_id: i64,
+    pub assisting_participant_ids: Vec<i64>,
+    pub bounty: i64,
+    pub building_type: String,
+    pub lane_type: String,
+    pub tower_type: Option<String>,
+    pub position: Position,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
+/// `team_id` is the team that lost the plate.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Position2 {
-    pub x: i64,
-    pub y: i64,
+#[serde(rename_all = "camelCase", default)]
+pub struct TurretPlateDestroyed {
+    pub timestamp: i64,
+    pub killer_id: i64,
+    pub team_id: i64,
+    pub lane_type: String,
+    pub position: Position,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-#[allow(non_camel_case_types)]
-pub struct n2 {
-    pub champion_stats: ChampionStats2,
-    pub current_gold: i64,
-    pub damage_stats: DamageStats2,
-    pub gold_per_second: i64,
-    pub jungle_minions_killed: i64,
-    pub level: i64,
-    pub minions_killed: i64,
+#[serde(rename_all = "camelCase", default)]
+pub struct SkillLevelUp {
+    pub timestamp: i64,
     pub participant_id: i64,
-    pub position: Position3,
-    pub time_enemy_spent_controlled: i64,
-    pub total_gold: i64,
-    pub xp: i64,
+    /// 1 to 4 for Q, W, E and R.
+    pub skill_slot: i64,
+    pub level_up_type: String,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ChampionStats2 {
-    pub ability_haste: i64,
-    pub ability_power: i64,
-    pub armor: i64,
-    pub armor_pen: i64,
-    pub armor_pen_percent: i64,
-    pub attack_damage: i64,
-    pub attack_speed: i64,
-    pub bonus_armor_pen_percent: i64,
-    pub bonus_magic_pen_percent: i64,
-    pub cc_reduction: i64,
-    pub cooldown_reduction: i64,
-    pub health: i64,
-    pub health_max: i64,
-    pub health_regen: i64,
-    pub lifesteal: i64,
-    pub magic_pen: i64,
-    pub magic_pen_percent: i64,
-    pub magic_resist: i64,
-    pub movement_speed: i64,
-    pub omnivamp: i64,
-    pub physical_vamp: i64,
-    pub power: i64,
-    pub power_max: i64,
-    pub power_regen: i64,
-    pub spell_vamp: i64,
+#[serde(rename_all = "camelCase", default)]
+pub struct LevelUp {
+    pub timestamp: i64,
+    pub participant_id: i64,
+    pub level: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DamageStats2 {
-    pub magic_damage_done: i64,
-    pub magic_damage_done_to_champions: i64,
-    pub magic_damage_taken: i64,
-    pub physical_damage_done: i64,
-    pub physical_damage_done_to_champions: i64,
-    pub physical_damage_taken: i64,
-    pub total_damage_done: i64,
-    pub total_damage_done_to_champions: i64,
-    pub total_damage_taken: i64,
-    pub true_damage_done: i64,
-    pub true_damage_done_to_champions: i64,
-    pub true_damage_taken: i64,
+#[serde(rename_all = "camelCase", default)]
+pub struct DragonSoulGiven {
+    pub timestamp: i64,
+    pub team_id: i64,
+    /// The soul's element, e.g. ``Infernal``.
+    pub name: String,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameEnd {
+    pub timestamp: i64,
+    pub real_timestamp: i64,
+    pub game_id: i64,
+    pub winning_team: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
+}
+
+/// An event of a kind the models don't know (pauses, objective bounties, or anything Riot adds
+/// later). Unlike elsewhere, its fields are serialized back out, since they're all it has.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UnknownEvent {
+    #[serde(rename = "type")]
+    pub type_field: String,
+    pub timestamp: i64,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Position3 {
+#[serde(rename_all = "camelCase", default)]
+pub struct Position {
     pub x: i64,
     pub y: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-#[allow(non_camel_case_types)]
-pub struct n3 {
-    pub champion_stats: ChampionStats3,
-    pub current_gold: i64,
-    pub damage_stats: DamageStats3,
-    pub gold_per_second: i64,
-    pub jungle_minions_killed: i64,
-    pub level: i64,
-    pub minions_killed: i64,
+#[serde(rename_all = "camelCase", default)]
+pub struct VictimDamageDealt {
+    pub basic: bool,
+    pub magic_damage: i64,
+    pub name: String,
     pub participant_id: i64,
-    pub position: Position4,
-    pub time_enemy_spent_controlled: i64,
-    pub total_gold: i64,
-    pub xp: i64,
+    pub physical_damage: i64,
+    pub spell_name: String,
+    pub spell_slot: i64,
+    pub true_damage: i64,
+    #[serde(rename = "type")]
+    pub type_field: String,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ChampionStats3 {
-    pub ability_haste: i64,
-    pub ability_power: i64,
-    pub armor: i64,
-    pub armor_pen: i64,
-    pub armor_pen_percent: i64,
-    pub attack_damage: i64,
-    pub attack_speed: i64,
-    pub bonus_armor_pen_percent: i64,
-    pub bonus_magic_pen_percent: i64,
-    pub cc_reduction: i64,
-    pub cooldown_reduction: i64,
-    pub health: i64,
-    pub health_max: i64,
-    pub health_regen: i64,
-    pub lifesteal: i64,
-    pub magic_pen: i64,
-    pub magic_pen_percent: i64,
-    pub magic_resist: i64,
-    pub movement_speed: i64,
-    pub omnivamp: i64,
-    pub physical_vamp: i64,
-    pub power: i64,
-    pub power_max: i64,
-    pub power_regen: i64,
-    pub spell_vamp: i64,
+#[serde(rename_all = "camelCase", default)]
+pub struct VictimDamageReceived {
+    pub basic: bool,
+    pub magic_damage: i64,
+    pub name: String,
+    pub participant_id: i64,
+    pub physical_damage: i64,
+    pub spell_name: String,
+    pub spell_slot: i64,
+    pub true_damage: i64,
+    #[serde(rename = "type")]
+    pub type_field: String,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
+/// Where every participant stood when a frame was taken, keyed by participant id: 1 to 5 are
+/// blue side and 6 to 10 red, each side in the order Riot lists them in the match.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DamageStats3 {
-    pub magic_damage_done: i64,
-    pub magic_damage_done_to_champions: i64,
-    pub magic_damage_taken: i64,
-    pub physical_damage_done: i64,
-    pub physical_damage_done_to_champions: i64,
-    pub physical_damage_taken: i64,
-    pub total_damage_done: i64,
-    pub total_damage_done_to_champions: i64,
-    pub total_damage_taken: i64,
-    pub true_damage_done: i64,
-    pub true_damage_done_to_champions: i64,
-    pub true_damage_taken: i64,
+#[serde(transparent)]
+pub struct ParticipantFrames(BTreeMap<i64, ParticipantFrame>);
+
+impl ParticipantFrames {
+    /// The frame for participant `id`, if they were in the game.
+    pub fn participant(&self, id: i64) -> Option<&ParticipantFrame> {
+        self.0.get(&id)
+    }
+
+    /// Every participant's frame, in participant id order.
+    pub fn iter(&self) -> impl Iterator<Item = &ParticipantFrame> {
+        self.0.values()
+    }
+
+    pub fn blue(&self) -> impl Iterator<Item = &ParticipantFrame> {
+        self.0.range(1..=5).map(|(_, frame)| frame)
+    }
+
+    pub fn red(&self) -> impl Iterator<Item = &ParticipantFrame> {
+        self.0.range(6..=10).map(|(_, frame)| frame)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Position4 {
-    pub x: i64,
-    pub y: i64,
+impl FromIterator<ParticipantFrame> for ParticipantFrames {
+    fn from_iter<I: IntoIterator<Item = ParticipantFrame>>(frames: I) -> Self {
+        Self(
+            frames
+                .into_iter()
+                .map(|frame| (frame.participant_id, frame))
+                .collect(),
+        )
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-#[allow(non_camel_case_types)]
-pub struct n4 {
-    pub champion_stats: ChampionStats4,
+#[serde(rename_all = "camelCase", default)]
+pub struct ParticipantFrame {
+    pub champion_stats: ChampionStats,
     pub current_gold: i64,
-    pub damage_stats: DamageStats4,
+    pub damage_stats: DamageStats,
     pub gold_per_second: i64,
     pub jungle_minions_killed: i64,
     pub level: i64,
     pub minions_killed: i64,
     pub participant_id: i64,
-    pub position: Position5,
+    pub position: Position,
     pub time_enemy_spent_controlled: i64,
     pub total_gold: i64,
     pub xp: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
+}
+
+impl ParticipantFrame {
+    /// Lane minions and jungle camps together.
+    pub fn cs(&self) -> i64 {
+        self.minions_killed + self.jungle_minions_killed
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ChampionStats4 {
+#[serde(rename_all = "camelCase", default)]
+pub struct ChampionStats {
     pub ability_haste: i64,
     pub ability_power: i64,
     pub armor: i64,
@@ -402,11 +435,13 @@ pub struct ChampionStats4 {
     pub power_max: i64,
     pub power_regen: i64,
     pub spell_vamp: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DamageStats4 {
+#[serde(rename_all = "camelCase", default)]
+pub struct DamageStats {
     pub magic_damage_done: i64,
     pub magic_damage_done_to_champions: i64,
     pub magic_damage_taken: i64,
@@ -419,450 +454,54 @@ pub struct DamageStats4 {
     pub true_damage_done: i64,
     pub true_damage_done_to_champions: i64,
     pub true_damage_taken: i64,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Position5 {
-    pub x: i64,
-    pub y: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-#[allow(non_camel_case_types)]
-pub struct n5 {
-    pub champion_stats: ChampionStats5,
-    pub current_gold: i64,
-    pub damage_stats: DamageStats5,
-    pub gold_per_second: i64,
-    pub jungle_minions_killed: i64,
-    pub level: i64,
-    pub minions_killed: i64,
-    pub participant_id: i64,
-    pub position: Position6,
-    pub time_enemy_spent_controlled: i64,
-    pub total_gold: i64,
-    pub xp: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ChampionStats5 {
-    pub ability_haste: i64,
-    pub ability_power: i64,
-    pub armor: i64,
-    pub armor_pen: i64,
-    pub armor_pen_percent: i64,
-    pub attack_damage: i64,
-    pub attack_speed: i64,
-    pub bonus_armor_pen_percent: i64,
-    pub bonus_magic_pen_percent: i64,
-    pub cc_reduction: i64,
-    pub cooldown_reduction: i64,
-    pub health: i64,
-    pub health_max: i64,
-    pub health_regen: i64,
-    pub lifesteal: i64,
-    pub magic_pen: i64,
-    pub magic_pen_percent: i64,
-    pub magic_resist: i64,
-    pub movement_speed: i64,
-    pub omnivamp: i64,
-    pub physical_vamp: i64,
-    pub power: i64,
-    pub power_max: i64,
-    pub power_regen: i64,
-    pub spell_vamp: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DamageStats5 {
-    pub magic_damage_done: i64,
-    pub magic_damage_done_to_champions: i64,
-    pub magic_damage_taken: i64,
-    pub physical_damage_done: i64,
-    pub physical_damage_done_to_champions: i64,
-    pub physical_damage_taken: i64,
-    pub total_damage_done: i64,
-    pub total_damage_done_to_champions: i64,
-    pub total_damage_taken: i64,
-    pub true_damage_done: i64,
-    pub true_damage_done_to_champions: i64,
-    pub true_damage_taken: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Position6 {
-    pub x: i64,
-    pub y: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-#[allow(non_camel_case_types)]
-pub struct n6 {
-    pub champion_stats: ChampionStats6,
-    pub current_gold: i64,
-    pub damage_stats: DamageStats6,
-    pub gold_per_second: i64,
-    pub jungle_minions_killed: i64,
-    pub level: i64,
-    pub minions_killed: i64,
-    pub participant_id: i64,
-    pub position: Position7,
-    pub time_enemy_spent_controlled: i64,
-    pub total_gold: i64,
-    pub xp: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ChampionStats6 {
-    pub ability_haste: i64,
-    pub ability_power: i64,
-    pub armor: i64,
-    pub armor_pen: i64,
-    pub armor_pen_percent: i64,
-    pub attack_damage: i64,
-    pub attack_speed: i64,
-    pub bonus_armor_pen_percent: i64,
-    pub bonus_magic_pen_percent: i64,
-    pub cc_reduction: i64,
-    pub cooldown_reduction: i64,
-    pub health: i64,
-    pub health_max: i64,
-    pub health_regen: i64,
-    pub lifesteal: i64,
-    pub magic_pen: i64,
-    pub magic_pen_percent: i64,
-    pub magic_resist: i64,
-    pub movement_speed: i64,
-    pub omnivamp: i64,
-    pub physical_vamp: i64,
-    pub power: i64,
-    pub power_max: i64,
-    pub power_regen: i64,
-    pub spell_vamp: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DamageStats6 {
-    pub magic_damage_done: i64,
-    pub magic_damage_done_to_champions: i64,
-    pub magic_damage_taken: i64,
-    pub physical_damage_done: i64,
-    pub physical_damage_done_to_champions: i64,
-    pub physical_damage_taken: i64,
-    pub total_damage_done: i64,
-    pub total_damage_done_to_champions: i64,
-    pub total_damage_taken: i64,
-    pub true_damage_done: i64,
-    pub true_damage_done_to_champions: i64,
-    pub true_damage_taken: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Position7 {
-    pub x: i64,
-    pub y: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-#[allow(non_camel_case_types)]
-pub struct n7 {
-    pub champion_stats: ChampionStats7,
-    pub current_gold: i64,
-    pub damage_stats: DamageStats7,
-    pub gold_per_second: i64,
-    pub jungle_minions_killed: i64,
-    pub level: i64,
-    pub minions_killed: i64,
-    pub participant_id: i64,
-    pub position: Position8,
-    pub time_enemy_spent_controlled: i64,
-    pub total_gold: i64,
-    pub xp: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ChampionStats7 {
-    pub ability_haste: i64,
-    pub ability_power: i64,
-    pub armor: i64,
-    pub armor_pen: i64,
-    pub armor_pen_percent: i64,
-    pub attack_damage: i64,
-    pub attack_speed: i64,
-    pub bonus_armor_pen_percent: i64,
-    pub bonus_magic_pen_percent: i64,
-    pub cc_reduction: i64,
-    pub cooldown_reduction: i64,
-    pub health: i64,
-    pub health_max: i64,
-    pub health_regen: i64,
-    pub lifesteal: i64,
-    pub magic_pen: i64,
-    pub magic_pen_percent: i64,
-    pub magic_resist: i64,
-    pub movement_speed: i64,
-    pub omnivamp: i64,
-    pub physical_vamp: i64,
-    pub power: i64,
-    pub power_max: i64,
-    pub power_regen: i64,
-    pub spell_vamp: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DamageStats7 {
-    pub magic_damage_done: i64,
-    pub magic_damage_done_to_champions: i64,
-    pub magic_damage_taken: i64,
-    pub physical_damage_done: i64,
-    pub physical_damage_done_to_champions: i64,
-    pub physical_damage_taken: i64,
-    pub total_damage_done: i64,
-    pub total_damage_done_to_champions: i64,
-    pub total_damage_taken: i64,
-    pub true_damage_done: i64,
-    pub true_damage_done_to_champions: i64,
-    pub true_damage_taken: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Position8 {
-    pub x: i64,
-    pub y: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-#[allow(non_camel_case_types)]
-pub struct n8 {
-    pub champion_stats: ChampionStats8,
-    pub current_gold: i64,
-    pub damage_stats: DamageStats8,
-    pub gold_per_second: i64,
-    pub jungle_minions_killed: i64,
-    pub level: i64,
-    pub minions_killed: i64,
-    pub participant_id: i64,
-    pub position: Position9,
-    pub time_enemy_spent_controlled: i64,
-    pub total_gold: i64,
-    pub xp: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ChampionStats8 {
-    pub ability_haste: i64,
-    pub ability_power: i64,
-    pub armor: i64,
-    pub armor_pen: i64,
-    pub armor_pen_percent: i64,
-    pub attack_damage: i64,
-    pub attack_speed: i64,
-    pub bonus_armor_pen_percent: i64,
-    pub bonus_magic_pen_percent: i64,
-    pub cc_reduction: i64,
-    pub cooldown_reduction: i64,
-    pub health: i64,
-    pub health_max: i64,
-    pub health_regen: i64,
-    pub lifesteal: i64,
-    pub magic_pen: i64,
-    pub magic_pen_percent: i64,
-    pub magic_resist: i64,
-    pub movement_speed: i64,
-    pub omnivamp: i64,
-    pub physical_vamp: i64,
-    pub power: i64,
-    pub power_max: i64,
-    pub power_regen: i64,
-    pub spell_vamp: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DamageStats8 {
-    pub magic_damage_done: i64,
-    pub magic_damage_done_to_champions: i64,
-    pub magic_damage_taken: i64,
-    pub physical_damage_done: i64,
-    pub physical_damage_done_to_champions: i64,
-    pub physical_damage_taken: i64,
-    pub total_damage_done: i64,
-    pub total_damage_done_to_champions: i64,
-    pub total_damage_taken: i64,
-    pub true_damage_done: i64,
-    pub true_damage_done_to_champions: i64,
-    pub true_damage_taken: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Position9 {
-    pub x: i64,
-    pub y: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-#[allow(non_camel_case_types)]
-pub struct n9 {
-    pub champion_stats: ChampionStats9,
-    pub current_gold: i64,
-    pub damage_stats: DamageStats9,
-    pub gold_per_second: i64,
-    pub jungle_minions_killed: i64,
-    pub level: i64,
-    pub minions_killed: i64,
-    pub participant_id: i64,
-    pub position: Position10,
-    pub time_enemy_spent_controlled: i64,
-    pub total_gold: i64,
-    pub xp: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ChampionStats9 {
-    pub ability_haste: i64,
-    pub ability_power: i64,
-    pub armor: i64,
-    pub armor_pen: i64,
-    pub armor_pen_percent: i64,
-    pub attack_damage: i64,
-    pub attack_speed: i64,
-    pub bonus_armor_pen_percent: i64,
-    pub bonus_magic_pen_percent: i64,
-    pub cc_reduction: i64,
-    pub cooldown_reduction: i64,
-    pub health: i64,
-    pub health_max: i64,
-    pub health_regen: i64,
-    pub lifesteal: i64,
-    pub magic_pen: i64,
-    pub magic_pen_percent: i64,
-    pub magic_resist: i64,
-    pub movement_speed: i64,
-    pub omnivamp: i64,
-    pub physical_vamp: i64,
-    pub power: i64,
-    pub power_max: i64,
-    pub power_regen: i64,
-    pub spell_vamp: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DamageStats9 {
-    pub magic_damage_done: i64,
-    pub magic_damage_done_to_champions: i64,
-    pub magic_damage_taken: i64,
-    pub physical_damage_done: i64,
-    pub physical_damage_done_to_champions: i64,
-    pub physical_damage_taken: i64,
-    pub total_damage_done: i64,
-    pub total_damage_done_to_champions: i64,
-    pub total_damage_taken: i64,
-    pub true_damage_done: i64,
-    pub true_damage_done_to_champions: i64,
-    pub true_damage_taken: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Position10 {
-    pub x: i64,
-    pub y: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-#[allow(non_camel_case_types)]
-pub struct n10 {
-    pub champion_stats: ChampionStats10,
-    pub current_gold: i64,
-    pub damage_stats: DamageStats10,
-    pub gold_per_second: i64,
-    pub jungle_minions_killed: i64,
-    pub level: i64,
-    pub minions_killed: i64,
-    pub participant_id: i64,
-    pub position: Position11,
-    pub time_enemy_spent_controlled: i64,
-    pub total_gold: i64,
-    pub xp: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ChampionStats10 {
-    pub ability_haste: i64,
-    pub ability_power: i64,
-    pub armor: i64,
-    pub armor_pen: i64,
-    pub armor_pen_percent: i64,
-    pub attack_damage: i64,
-    pub attack_speed: i64,
-    pub bonus_armor_pen_percent: i64,
-    pub bonus_magic_pen_percent: i64,
-    pub cc_reduction: i64,
-    pub cooldown_reduction: i64,
-    pub health: i64,
-    pub health_max: i64,
-    pub health_regen: i64,
-    pub lifesteal: i64,
-    pub magic_pen: i64,
-    pub magic_pen_percent: i64,
-    pub magic_resist: i64,
-    pub movement_speed: i64,
-    pub omnivamp: i64,
-    pub physical_vamp: i64,
-    pub power: i64,
-    pub power_max: i64,
-    pub power_regen: i64,
-    pub spell_vamp: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DamageStats10 {
-    pub magic_damage_done: i64,
-    pub magic_damage_done_to_champions: i64,
-    pub magic_damage_taken: i64,
-    pub physical_damage_done: i64,
-    pub physical_damage_done_to_champions: i64,
-    pub physical_damage_taken: i64,
-    pub total_damage_done: i64,
-    pub total_damage_done_to_champions: i64,
-    pub total_damage_taken: i64,
-    pub true_damage_done: i64,
-    pub true_damage_done_to_champions: i64,
-    pub true_damage_taken: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Position11 {
-    pub x: i64,
-    pub y: i64,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct Participant {
     pub participant_id: i64,
     pub puuid: String,
+    #[serde(flatten, skip_serializing)]
+    pub extra: Extra,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn keeps_unknown_event_types() {
+        let raw =
+            json!({ "type": "FEAT_UPDATE", "timestamp": 754_000, "featType": 2, "teamId": 100 });
+        let event: Event = serde_json::from_value(raw.clone()).unwrap();
+        let Event::Unknown(unknown) = &event else {
+            panic!("expected an unknown event, got {event:?}");
+        };
+        assert_eq!(event.kind(), "FEAT_UPDATE");
+        assert_eq!(event.timestamp(), 754_000);
+        assert_eq!(unknown.extra.get("featType"), Some(&json!(2)));
+        // Everything it came with goes back out.
+        assert_eq!(serde_json::to_value(&event).unwrap(), raw);
+    }
+
+    #[test]
+    fn reads_known_event_types_with_fields_the_model_lacks() {
+        let event: Event = serde_json::from_value(json!({
+            "type": "ITEM_PURCHASED",
+            "timestamp": 61_000,
+            "participantId": 4,
+            "itemId": 1055,
+            "goldSpent": 450,
+        }))
+        .unwrap();
+        let Event::ItemPurchased(purchase) = event else {
+            panic!("expected a purchase, got {event:?}");
+        };
+        assert_eq!((purchase.participant_id, purchase.item_id), (4, 1055));
+        assert_eq!(purchase.extra.get("goldSpent"), Some(&json!(450)));
+    }
 }
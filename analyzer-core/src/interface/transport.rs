@@ -0,0 +1,268 @@
+use std::collections::HashMap;
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+
+use crate::api_error::ApiError;
+
+/// Status code, headers and body of a single response from Riot's API (or something pretending
+/// to be it). Header names are stored lowercase.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+impl Response {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+}
+
+/// A request path with the query string cut off, for naming it in errors.
+pub fn endpoint(path: &str) -> &str {
+    path.split('?').next().unwrap_or_default()
+}
+
+/// A future that isn't required to be `Send`, since nothing in the browser is.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// Whatever [`Interface`](crate::interface::Interface) uses to reach Riot. `host` is the routing
+/// value (`americas`, `na1`, ...) and `path` is everything after it, query string included.
+pub trait Transport: Send + Sync {
+    fn get(&self, host: &str, path: &str, api_key: &str) -> Result<Response, ApiError>;
+
+    /// The same request as a future; this is what [`Interface`](crate::interface::Interface)
+    /// actually calls. By default it just runs [`Transport::get`], which is fine anywhere
+    /// blocking is: a native worker thread, or files on disk.
+    fn get_async<'a>(
+        &'a self,
+        host: &'a str,
+        path: &'a str,
+        api_key: &'a str,
+    ) -> BoxFuture<'a, Result<Response, ApiError>> {
+        Box::pin(async move { self.get(host, path, api_key) })
+    }
+}
+
+/// The default transport: a reqwest client talking to ``*.api.riotgames.com``. Natively it
+/// blocks; in the browser only [`Transport::get_async`] works, on top of ``fetch``.
+#[derive(Default)]
+pub struct ReqwestTransport {
+    #[cfg(not(target_arch = "wasm32"))]
+    client: reqwest::blocking::Client,
+    #[cfg(target_arch = "wasm32")]
+    client: reqwest::Client,
+    /// Sends every request here instead, whatever its routing host, e.g. to a local mock server.
+    base_url: Option<String>,
+}
+
+impl ReqwestTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A transport that sends everything to `base_url` (e.g. ``http://127.0.0.1:8080``) rather
+    /// than Riot.
+    pub fn with_base_url(base_url: &str) -> Self {
+        Self {
+            base_url: Some(base_url.trim_end_matches('/').to_owned()),
+            ..Default::default()
+        }
+    }
+
+    fn url(&self, host: &str, path: &str) -> String {
+        match &self.base_url {
+            Some(base) => format!("{base}{path}"),
+            None => format!("https://{host}.api.riotgames.com{path}"),
+        }
+    }
+
+    fn headers(headers: &reqwest::header::HeaderMap) -> HashMap<String, String> {
+        headers
+            .iter()
+            .filter_map(|(name, value)| {
+                value
+                    .to_str()
+                    .ok()
+                    .map(|v| (name.as_str().to_owned(), v.to_owned()))
+            })
+            .collect()
+    }
+}
+
+fn network_error(path: &str) -> impl Fn(reqwest::Error) -> ApiError + '_ {
+    move |source| ApiError::Network {
+        endpoint: endpoint(path).to_owned(),
+        source,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Transport for ReqwestTransport {
+    fn get(&self, host: &str, path: &str, api_key: &str) -> Result<Response, ApiError> {
+        let resp = self
+            .client
+            .get(self.url(host, path))
+            .header("X-Riot-Token", api_key)
+            .send()
+            .map_err(network_error(path))?;
+        Ok(Response {
+            status: resp.status().as_u16(),
+            headers: Self::headers(resp.headers()),
+            body: resp.text().map_err(network_error(path))?,
+        })
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Transport for ReqwestTransport {
+    fn get(&self, _host: &str, _path: &str, _api_key: &str) -> Result<Response, ApiError> {
+        Err(ApiError::new(
+            "Blocking requests aren't available in the browser",
+        ))
+    }
+
+    fn get_async<'a>(
+        &'a self,
+        host: &'a str,
+        path: &'a str,
+        api_key: &'a str,
+    ) -> BoxFuture<'a, Result<Response, ApiError>> {
+        Box::pin(async move {
+            let resp = self
+                .client
+                .get(self.url(host, path))
+                .header("X-Riot-Token", api_key)
+                .send()
+                .await
+                .map_err(network_error(path))?;
+            Ok(Response {
+                status: resp.status().as_u16(),
+                headers: Self::headers(resp.headers()),
+                body: resp.text().await.map_err(network_error(path))?,
+            })
+        })
+    }
+}
+
+/// Serves recorded responses from a directory instead of the network. A request for
+/// ``/lol/match/v5/matches/NA1_123/timeline?foo=bar`` is answered with the contents of
+/// ``{root}/lol/match/v5/matches/NA1_123/timeline.json``; the routing host is ignored. Anything
+/// without a file behind it is a 404, same as the real API. The only query parameters honoured
+/// are `start` and `count`, which page through a recorded JSON array the way match-v5 pages
+/// through match ids.
+pub struct FixtureTransport {
+    root: PathBuf,
+}
+
+impl FixtureTransport {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// File a request path maps to inside the fixture directory.
+    pub fn fixture_path(root: &Path, path: &str) -> PathBuf {
+        root.join(endpoint(path).trim_start_matches('/'))
+            .with_extension("json")
+    }
+
+    /// Applies `start`/`count` from the query string to a JSON array body.
+    fn page(body: String, path: &str) -> String {
+        let query = path.split_once('?').map(|(_, q)| q).unwrap_or_default();
+        let param = |name: &str| {
+            query
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(k, _)| *k == name)
+                .and_then(|(_, v)| v.parse::<usize>().ok())
+        };
+        if param("start").is_none() && param("count").is_none() {
+            return body;
+        }
+        match serde_json::from_str::<Vec<serde_json::Value>>(&body) {
+            Ok(items) => {
+                let page: Vec<_> = items
+                    .into_iter()
+                    .skip(param("start").unwrap_or(0))
+                    .take(param("count").unwrap_or(usize::MAX))
+                    .collect();
+                serde_json::to_string(&page).unwrap_or(body)
+            }
+            Err(_) => body,
+        }
+    }
+}
+
+impl Transport for FixtureTransport {
+    fn get(&self, _host: &str, path: &str, _api_key: &str) -> Result<Response, ApiError> {
+        match fs::read_to_string(Self::fixture_path(&self.root, path)) {
+            Ok(body) => Ok(Response {
+                status: 200,
+                body: Self::page(body, path),
+                ..Default::default()
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Response {
+                status: 404,
+                ..Default::default()
+            }),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Wraps another transport and writes every successful response into a fixture directory laid
+/// out the way [`FixtureTransport`] reads it, so a real account can be replayed offline later.
+pub struct RecordingTransport<T: Transport> {
+    inner: T,
+    root: PathBuf,
+}
+
+impl<T: Transport> RecordingTransport<T> {
+    pub fn new(inner: T, root: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            root: root.into(),
+        }
+    }
+
+    fn record(&self, path: &str, resp: &Response) -> Result<(), ApiError> {
+        if resp.is_success() {
+            let file = FixtureTransport::fixture_path(&self.root, path);
+            if let Some(dir) = file.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(file, &resp.body)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Transport> Transport for RecordingTransport<T> {
+    fn get(&self, host: &str, path: &str, api_key: &str) -> Result<Response, ApiError> {
+        let resp = self.inner.get(host, path, api_key)?;
+        self.record(path, &resp)?;
+        Ok(resp)
+    }
+
+    fn get_async<'a>(
+        &'a self,
+        host: &'a str,
+        path: &'a str,
+        api_key: &'a str,
+    ) -> BoxFuture<'a, Result<Response, ApiError>> {
+        Box::pin(async move {
+            let resp = self.inner.get_async(host, path, api_key).await?;
+            self.record(path, &resp)?;
+            Ok(resp)
+        })
+    }
+}
@@ -1,55 +1,67 @@
 use crate::api_error::ApiError;
 use crate::interface::Interface;
 use crate::player::{PlayerIdent, Summoner};
+use crate::riot_id::RiotId;
 
 use serde::Deserialize;
 
+/// Who a PUUID belongs to right now, as account-v1 tells it. The PUUID never changes; the Riot
+/// ID can, whenever the player pays to rename.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Account {
+    pub puuid: String,
+    pub game_name: String,
+    pub tag_line: String,
+}
+
 impl Interface {
-    pub fn request_player_data(
-        &self,
-        game_name: String,
-        tagline: String,
-    ) -> Result<PlayerIdent, ApiError> {
-        #[derive(Deserialize)]
-        #[serde(rename_all = "camelCase")]
-        struct Account {
-            pub puuid: String,
-            pub game_name: String,
-            pub tag_line: String,
-        }
-        let account = reqwest::blocking::get(format!(
-            "https://{}.api.riotgames.com/riot/account/v1/accounts/by-riot-id/{}/{}?api_key={}",
-            Self::get_server(&self.server),
-            game_name,
-            tagline,
-            self.api_key
-        ))?
-        .json::<Account>()?;
-
-        let summ = reqwest::blocking::get(format!(
-            "https://{}.api.riotgames.com/lol/summoner/v4/summoners/by-puuid/{}?api_key={}",
-            Self::get_legacy_server(self.server.as_str()),
-            account.puuid,
-            self.api_key
-        ))? //figure out the server for the url. Maybe local match?
-        .json::<Summoner>()?;
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn request_player_data(&self, id: &RiotId) -> Result<PlayerIdent, ApiError> {
+        pollster::block_on(self.request_player_data_async(id))
+    }
+
+    /// Resolves `id` to an account on this interface's shard. The returned name and tag are
+    /// Riot's spelling, which may differ in case from what was typed.
+    pub async fn request_player_data_async(&self, id: &RiotId) -> Result<PlayerIdent, ApiError> {
+        let (game_name, tag_line) = id.encoded();
+        let account: Account = self
+            .fetch_json(
+                self.server.account_regional(),
+                "account-v1.getByRiotId",
+                &format!("/riot/account/v1/accounts/by-riot-id/{game_name}/{tag_line}"),
+            )
+            .await?;
+
+        let summ: Summoner = self
+            .fetch_json(
+                self.server.platform(),
+                "summoner-v4.getByPUUID",
+                &format!("/lol/summoner/v4/summoners/by-puuid/{}", account.puuid),
+            )
+            .await?;
 
         Ok(PlayerIdent {
             summoner: summ,
             game_name: account.game_name,
             tagline: account.tag_line,
-            server: self.server.clone(),
+            server: self.server,
+            unknown_server: None,
         })
     }
 
-    pub fn get_legacy_server(server: &str) -> &str {
-        match server {
-            "NA" => "na1",
-            "BR" => "br1",
-            "EUW" => "euw1",
-            "EUNE" => "eun1",
-            "OCE" => "oc1",
-            &_ => "na1",
-        }
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn request_account(&self, puuid: &str) -> Result<Account, ApiError> {
+        pollster::block_on(self.request_account_async(puuid))
+    }
+
+    /// The account behind `puuid`, under its current Riot ID.
+    pub async fn request_account_async(&self, puuid: &str) -> Result<Account, ApiError> {
+        self.fetch_json(
+            self.server.account_regional(),
+            "account-v1.getByPuuid",
+            &format!("/riot/account/v1/accounts/by-puuid/{puuid}"),
+        )
+        .await
     }
 }
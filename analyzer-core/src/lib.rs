@@ -1,17 +1,23 @@
 pub mod api_error;
 pub mod data_processor;
+pub mod import;
 pub mod interface;
 pub mod player;
+pub mod queue;
+pub mod region;
+pub mod riot_id;
 pub mod save;
 
 use serde_derive::{Deserialize, Serialize};
+
+use crate::region::Region;
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StartData {
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub api_key: String,
     #[serde(rename = "PUUID")]
     pub puuid: String,
     pub start_date: i64,
-    pub region: String,
+    pub region: Region,
 }
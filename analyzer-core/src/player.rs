@@ -1,8 +1,14 @@
-use crate::data_processor::Games;
+use crate::data_processor::{Checkpoint, Games, GraphData, RawData};
 use crate::interface::Interface;
+use crate::interface::game_interface::MatchQuery;
+use crate::interface::ranked_data::{RankSnapshot, RankedData};
+use crate::queue::QueueType;
+use crate::region::Region;
+use crate::riot_id::RiotId;
 use crate::{StartData, api_error::ApiError};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Deserialize, Serialize, Clone)]
 pub struct Player {
@@ -11,14 +17,124 @@ pub struct Player {
     pub games: Games,
     pub interface: Interface,
     pub max_games: usize,
+    #[serde(default)]
+    pub import_policy: ImportPolicy,
+    /// Queues this profile imports games from.
+    #[serde(default = "Player::default_queues")]
+    pub queues: Vec<QueueType>,
+    /// Ladder standing taken on every load, oldest first. A new snapshot is only kept when
+    /// something changed since the last one for that queue.
+    #[serde(default)]
+    pub rank_history: Vec<RankSnapshot>,
+    /// Why the last rank lookup failed, if it did. Not saved.
+    #[serde(skip)]
+    pub rank_error: Option<String>,
+    /// Matches that couldn't be fetched last time for reasons that may since have passed (a
+    /// dropped connection, a 5xx). Every refresh asks for them again alongside the new games.
+    #[serde(default)]
+    pub pending_ids: Vec<String>,
+    /// Minutes of each game at which the player is compared with their lane opponent.
+    #[serde(default = "Player::default_checkpoints")]
+    pub checkpoints: Vec<u32>,
+}
+
+/// How much history a brand new profile pulls in on its first load. Later refreshes only ever
+/// fetch what was played after the newest stored game.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub enum ImportPolicy {
+    /// Every game played since this unix timestamp (seconds).
+    Since(i64),
+    /// The most recent `n` games, however far back that goes.
+    LastGames(usize),
+}
+
+impl Default for ImportPolicy {
+    fn default() -> Self {
+        ImportPolicy::LastGames(30)
+    }
+}
+
+impl ImportPolicy {
+    pub fn query(&self) -> MatchQuery {
+        match self {
+            ImportPolicy::Since(start) => MatchQuery::since(*start),
+            ImportPolicy::LastGames(n) => MatchQuery::last(*n),
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize, Default, Debug, Clone, PartialEq)]
+#[serde(from = "SavedIdent", into = "SavedIdent")]
 pub struct PlayerIdent {
     pub summoner: Summoner,
     pub game_name: String,
     pub tagline: String,
-    pub server: String,
+    pub server: Region,
+    /// The region code the profile was saved under, if it isn't one Riot still serves (``CN``
+    /// and ``NONE`` from when the region picker offered them). `server` is only a stand-in
+    /// then, and nothing should be asked of Riot until the player is looked up again.
+    pub unknown_server: Option<String>,
+}
+
+/// [`PlayerIdent`] as saved, with the region code kept as written.
+#[derive(Deserialize, Serialize)]
+struct SavedIdent {
+    summoner: Summoner,
+    game_name: String,
+    tagline: String,
+    server: String,
+}
+
+impl From<SavedIdent> for PlayerIdent {
+    fn from(saved: SavedIdent) -> Self {
+        let (server, unknown_server) = match saved.server.parse() {
+            Ok(server) => (server, None),
+            Err(_) => (Region::default(), Some(saved.server)),
+        };
+        PlayerIdent {
+            summoner: saved.summoner,
+            game_name: saved.game_name,
+            tagline: saved.tagline,
+            server,
+            unknown_server,
+        }
+    }
+}
+
+impl From<PlayerIdent> for SavedIdent {
+    fn from(ident: PlayerIdent) -> Self {
+        SavedIdent {
+            server: ident
+                .unknown_server
+                .unwrap_or_else(|| ident.server.to_string()),
+            summoner: ident.summoner,
+            game_name: ident.game_name,
+            tagline: ident.tagline,
+        }
+    }
+}
+
+impl PlayerIdent {
+    /// The shard to ask Riot about this player on, unless the profile was saved under a region
+    /// that no longer exists.
+    pub fn known_server(&self) -> Result<Region, ApiError> {
+        match &self.unknown_server {
+            None => Ok(self.server),
+            Some(code) => Err(ApiError::new(&format!(
+                "This profile was saved for region {code}, which Riot no longer serves. Search \
+                 for the player under the region they play on now."
+            ))),
+        }
+    }
+
+    /// The player's Riot ID as Riot spells it.
+    pub fn riot_id(&self) -> RiotId {
+        RiotId {
+            game_name: self.game_name.clone(),
+            tag_line: self.tagline.clone(),
+            region: self.server,
+        }
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -38,15 +154,35 @@ impl Default for Player {
             games: Games::default(),
             interface: Interface::default(),
             max_games: 30,
+            import_policy: ImportPolicy::default(),
+            queues: Player::default_queues(),
+            rank_history: Vec::new(),
+            rank_error: None,
+            pending_ids: Vec::new(),
+            checkpoints: Player::default_checkpoints(),
         }
     }
 }
 
 #[allow(clippy::allow_attributes, clippy::redundant_field_names)]
 impl Player {
-    pub fn new(raw_username: &str, api_key: String) -> Result<Player, ApiError> {
-        let mut inter = Interface::new(&api_key);
-        println!("Creating new player {}", raw_username);
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new(id: &RiotId, api_key: String) -> Result<Player, ApiError> {
+        Self::with_interface(id, Interface::new(&api_key))
+    }
+
+    /// Same as [`Player::new`], but every request goes through the given interface. Handy for
+    /// pointing a player at recorded fixtures instead of the live API.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_interface(id: &RiotId, inter: Interface) -> Result<Player, ApiError> {
+        pollster::block_on(Self::with_interface_async(id, inter))
+    }
+
+    pub async fn with_interface_async(
+        id: &RiotId,
+        mut inter: Interface,
+    ) -> Result<Player, ApiError> {
+        let api_key = inter.api_key();
         //let start_of_day = Utc::now()
         //    .date_naive()
         //    .and_hms_opt(0, 0, 0)
@@ -55,72 +191,234 @@ impl Player {
         //    .unwrap()
         //    .timestamp();
         let start_of_day = Utc::now().timestamp() - 86400;
-        let ident = inter.gen_player_ident_from_string(raw_username)?;
+        let ident = inter.lookup_riot_id_async(id).await?;
         Ok(Player {
             ident: ident.clone(),
             start_data: StartData {
                 api_key: api_key,
                 puuid: ident.summoner.puuid.clone(),
                 start_date: start_of_day,
-                region: ident.server.clone(),
+                region: ident.server,
             },
             games: Games::default(),
             interface: inter,
             max_games: 30,
+            import_policy: ImportPolicy::default(),
+            queues: Player::default_queues(),
+            rank_history: Vec::new(),
+            rank_error: None,
+            pending_ids: Vec::new(),
+            checkpoints: Player::default_checkpoints(),
         })
     }
 
+    /// A profile for games imported from disk, built without asking Riot anything. See
+    /// [`crate::import`].
+    pub fn offline(ident: PlayerIdent) -> Player {
+        Player {
+            start_data: StartData {
+                puuid: ident.summoner.puuid.clone(),
+                region: ident.server,
+                ..Default::default()
+            },
+            ident,
+            ..Default::default()
+        }
+    }
+
+    /// Adds imported games, skipping any already stored. Returns how many were new. Unlike
+    /// fetched games these aren't trimmed to [`Player::max_games`]; whoever imported them
+    /// wanted them.
+    pub fn import_games(&mut self, games: Vec<RawData>) -> usize {
+        let added = self.games.append_games(games);
+        self.sort_games();
+        added
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn load_new_player(&mut self) -> Result<(), ApiError> {
-        let mut time = self.start_data.start_date;
-        let mut game_ids = self
+        pollster::block_on(self.load_new_player_async())
+    }
+
+    pub async fn load_new_player_async(&mut self) -> Result<(), ApiError> {
+        let game_ids = self
             .interface
-            .get_game_ids(&time.to_string(), &self.start_data.puuid)?;
-        //TODO: check if we have fewer than 15 games, then check again with backed up timestamp
-        let mut count = 0;
-        while game_ids.len() < 10 {
-            time -= 86400;
-            if count > 14 {
-                return Err(ApiError::new("Player does not have enough games"));
-            }
-            count += 1;
-            game_ids = self
-                .interface
-                .get_game_ids(&time.to_string(), &self.start_data.puuid)?;
-        }
-        self.games = Games::new(
-            self.interface
-                .get_match_data_collection(game_ids, &self.start_data.puuid)?,
-        );
+            .get_game_ids_async(
+                &self.import_policy.query().in_queues(&self.queues),
+                &self.start_data.puuid,
+            )
+            .await?;
+        if game_ids.is_empty() {
+            return Err(ApiError::new("Player does not have any games to import"));
+        }
+        let batch = self
+            .interface
+            .get_match_data_collection_async(game_ids, &self.start_data.puuid, &self.checkpoints)
+            .await;
+        self.pending_ids = batch.retry_ids();
+        self.games = Games::new(batch.into_games()?);
+        self.try_record_rank_async().await;
         Ok(())
     }
 
     pub fn load_indexed_player(&mut self, player_as_string: String) -> Result<(), ApiError> {
         //println!("entered load_indexed_player: {player_as_string}");
         let save: Player = serde_json::from_str(&player_as_string)?;
-        let inter = Interface::new_with_server(&self.start_data.api_key, save.ident.server.clone());
+        // Keep whatever key, transport and cache the caller set up; only the shard comes from
+        // the save.
+        let inter = self.interface.clone().with_server(save.ident.server);
         self.ident = save.ident;
         self.start_data = save.start_data;
         self.games = save.games;
+        self.games.upgrade_legacy();
+        self.import_policy = save.import_policy;
+        self.queues = save.queues;
+        self.rank_history = save.rank_history;
+        self.pending_ids = save.pending_ids;
+        self.checkpoints = save.checkpoints;
         self.interface = inter;
         self.max_games = 30;
         Ok(())
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn load_new_games(&mut self) -> Result<bool, ApiError> {
-        let new_games = self.interface.get_game_ids(
-            &self.games.last_game_end().to_string(),
-            &self.start_data.puuid,
-        )?;
-        if !new_games.is_empty() {
-            self.games.append_games(
-                self.interface
-                    .get_match_data_collection(new_games, &self.start_data.puuid)?,
-            );
-            self.trim_games();
-            self.sort_games();
-            return Ok(true);
-        }
-        Ok(false)
+        pollster::block_on(self.load_new_games_async())
+    }
+
+    pub async fn load_new_games_async(&mut self) -> Result<bool, ApiError> {
+        self.try_record_rank_async().await;
+        // game_end is in milliseconds, match-v5 wants seconds
+        let query = match self.games.last_game_end() {
+            Some(end) => MatchQuery::since(end / 1000 + 1),
+            None => self.import_policy.query(),
+        }
+        .in_queues(&self.queues);
+        let mut ids = self
+            .interface
+            .get_game_ids_async(&query, &self.start_data.puuid)
+            .await?;
+        for id in &self.pending_ids {
+            if !ids.contains(id) {
+                ids.push(id.clone());
+            }
+        }
+        if ids.is_empty() {
+            return Ok(false);
+        }
+
+        // Whatever did come back is kept even if some matches failed; those are remembered and
+        // tried again next time rather than costing the whole refresh.
+        let batch = self
+            .interface
+            .get_match_data_collection_async(ids, &self.start_data.puuid, &self.checkpoints)
+            .await;
+        // Every pending id was asked for again above, so what failed this time is the whole
+        // list. It's taken before the games so it survives a batch where nothing came back.
+        self.pending_ids = batch.retry_ids();
+        let new_games = batch.into_games()?;
+        if self.games.append_games(new_games) == 0 {
+            return Ok(false);
+        }
+        self.trim_games();
+        self.sort_games();
+        Ok(true)
+    }
+
+    /// Asks account-v1 who this player is now and takes on their current Riot ID. `true` if it
+    /// had changed since the profile was last saved.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn refresh_riot_id(&mut self) -> Result<bool, ApiError> {
+        pollster::block_on(self.refresh_riot_id_async())
+    }
+
+    pub async fn refresh_riot_id_async(&mut self) -> Result<bool, ApiError> {
+        let account = self
+            .interface
+            .request_account_async(&self.start_data.puuid)
+            .await?;
+        let changed =
+            account.game_name != self.ident.game_name || account.tag_line != self.ident.tagline;
+        self.ident.game_name = account.game_name;
+        self.ident.tagline = account.tag_line;
+        Ok(changed)
+    }
+
+    /// Folds in another saved profile of the same account, e.g. one left behind under an old
+    /// Riot ID. Games and rank history from both are kept; everything else is this profile's.
+    pub fn merge(&mut self, other: Player) {
+        self.games.merge(other.games);
+
+        for snapshot in other.rank_history {
+            if !self.rank_history.contains(&snapshot) {
+                self.rank_history.push(snapshot);
+            }
+        }
+        self.rank_history.sort_by_key(|s| s.taken_at);
+
+        for id in other.pending_ids {
+            if !self.pending_ids.contains(&id) {
+                self.pending_ids.push(id);
+            }
+        }
+    }
+
+    /// Fetches the current league entries and appends any that moved since the last snapshot.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn record_rank(&mut self) -> Result<(), ApiError> {
+        pollster::block_on(self.record_rank_async())
+    }
+
+    pub async fn record_rank_async(&mut self) -> Result<(), ApiError> {
+        let entries = self
+            .interface
+            .request_ranked_data_async(&self.start_data.puuid)
+            .await?;
+        let now = Utc::now().timestamp_millis();
+        for entry in entries {
+            let Some(queue) = entry.queue() else {
+                continue;
+            };
+            let unchanged = self
+                .rank_history
+                .iter()
+                .rev()
+                .find(|s| s.queue == queue)
+                .is_some_and(|s| s.same_standing(&entry));
+            if !unchanged {
+                self.rank_history.push(RankSnapshot {
+                    taken_at: now,
+                    queue,
+                    entry,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// [`Player::record_rank_async`], keeping any failure in [`Player::rank_error`] instead of
+    /// returning it. Rank history is a nicety; a league-v4 hiccup shouldn't cost a load its
+    /// match data.
+    pub async fn try_record_rank_async(&mut self) {
+        self.rank_error = self.record_rank_async().await.err().map(|e| e.to_string());
+    }
+
+    /// The most recent league entry for `queue`, if the player has ever been seen ranked in it.
+    pub fn current_rank(&self, queue: QueueType) -> Option<&RankedData> {
+        self.rank_history
+            .iter()
+            .rev()
+            .find(|s| s.queue == queue)
+            .map(|s| &s.entry)
+    }
+
+    /// `(taken_at, total LP)` for every snapshot in `queue`, oldest first.
+    pub fn lp_points(&self, queue: QueueType) -> Vec<(i64, i64)> {
+        self.rank_history
+            .iter()
+            .filter(|s| s.queue == queue)
+            .map(|s| (s.taken_at, s.entry.total_lp()))
+            .collect()
     }
 
     fn trim_games(&mut self) {
@@ -133,7 +431,10 @@ impl Player {
         self.games.sort_games();
     }
 
+    /// Rotates the key this player's requests go out with. Every interface sharing the same
+    /// [`ApiKey`](crate::interface::api_key::ApiKey) picks it up too.
     pub fn set_api(&mut self, api_key: String) {
+        self.interface.api_key_handle().set(&api_key);
         self.start_data.api_key = api_key;
     }
 
@@ -145,35 +446,120 @@ impl Player {
         self.clone().games.is_empty()
     }
 
-    pub fn gd15_points(&self) -> Vec<i32> {
-        let mut out = Vec::new();
-        for g in &self.games.games {
-            out.push(g.graph_data.gd15);
+    fn default_queues() -> Vec<QueueType> {
+        vec![QueueType::RankedSolo]
+    }
+
+    fn default_checkpoints() -> Vec<u32> {
+        Checkpoint::MINUTES.to_vec()
+    }
+
+    /// Graph data for every stored game, or only those played in `queue`.
+    fn graph_data(&self, queue: Option<QueueType>) -> impl Iterator<Item = &GraphData> {
+        self.games
+            .games
+            .iter()
+            .map(|g| &g.graph_data)
+            .filter(move |g| queue.is_none_or(|q| g.queue == q))
+    }
+
+    /// GD at `minute` for each game, `None` for games that ended before it or were stored
+    /// before it was a checkpoint.
+    pub fn gd_points(&self, queue: Option<QueueType>, minute: u32) -> Vec<Option<i32>> {
+        self.checkpoint_points(queue, minute, |g| &g.gd)
+    }
+
+    /// XP difference at `minute` for each game; see [`Player::gd_points`].
+    pub fn xpd_points(&self, queue: Option<QueueType>, minute: u32) -> Vec<Option<i32>> {
+        self.checkpoint_points(queue, minute, |g| &g.xpd)
+    }
+
+    /// CS difference at `minute` for each game; see [`Player::gd_points`].
+    pub fn csd_points(&self, queue: Option<QueueType>, minute: u32) -> Vec<Option<i32>> {
+        self.checkpoint_points(queue, minute, |g| &g.csd)
+    }
+
+    /// Level difference at `minute` for each game; see [`Player::gd_points`].
+    pub fn level_diff_points(&self, queue: Option<QueueType>, minute: u32) -> Vec<Option<i32>> {
+        self.checkpoint_points(queue, minute, |g| &g.level_diff)
+    }
+
+    fn checkpoint_points(
+        &self,
+        queue: Option<QueueType>,
+        minute: u32,
+        diffs: impl Fn(&GraphData) -> &BTreeMap<u32, Option<i32>>,
+    ) -> Vec<Option<i32>> {
+        self.graph_data(queue)
+            .map(|g| diffs(g).get(&minute).copied().flatten())
+            .collect()
+    }
+
+    /// Every minute GD can be shown at: the profile's checkpoints, and any that older games
+    /// were stored with.
+    pub fn checkpoint_minutes(&self) -> Vec<u32> {
+        let mut minutes = self.checkpoints.clone();
+        for g in self.graph_data(None) {
+            minutes.extend(g.gd.keys());
         }
-        out
+        minutes.sort_unstable();
+        minutes.dedup();
+        minutes
     }
 
-    pub fn csm_points(&self) -> Vec<f32> {
+    pub fn csm_points(&self, queue: Option<QueueType>) -> Vec<f32> {
         let mut out = Vec::new();
-        for g in &self.games.games {
-            out.push(g.graph_data.csm);
+        for g in self.graph_data(queue) {
+            out.push(g.csm);
         }
         out
     }
 
-    pub fn dpm_points(&self) -> Vec<f32> {
+    pub fn dpm_points(&self, queue: Option<QueueType>) -> Vec<f32> {
         let mut out = Vec::new();
-        for g in &self.games.games {
-            out.push(g.graph_data.dpm);
+        for g in self.graph_data(queue) {
+            out.push(g.dpm);
         }
         out
     }
 
-    pub fn kp_points(&self) -> Vec<f32> {
+    pub fn kp_points(&self, queue: Option<QueueType>) -> Vec<f32> {
         let mut out = Vec::new();
-        for g in &self.games.games {
-            out.push(g.graph_data.kp);
+        for g in self.graph_data(queue) {
+            out.push(g.kp);
         }
         out
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn saved_ident(server: &str) -> serde_json::Value {
+        json!({
+            "summoner": { "puuid": "abc", "profileIconId": 1, "revisionDate": 0, "summonerLevel": 30 },
+            "game_name": "Jade",
+            "tagline": "NA1",
+            "server": server,
+        })
+    }
+
+    #[test]
+    fn keeps_a_region_that_no_longer_exists() {
+        let ident: PlayerIdent = serde_json::from_value(saved_ident("CN")).unwrap();
+        assert_eq!(ident.unknown_server.as_deref(), Some("CN"));
+        assert!(ident.known_server().is_err());
+        // Saving it again doesn't quietly turn it into the stand-in.
+        assert_eq!(serde_json::to_value(&ident).unwrap(), saved_ident("CN"));
+    }
+
+    #[test]
+    fn reads_known_regions() {
+        let ident: PlayerIdent = serde_json::from_value(saved_ident("EUW")).unwrap();
+        assert_eq!(ident.known_server().unwrap(), Region::EUW);
+        assert_eq!(serde_json::to_value(&ident).unwrap(), saved_ident("EUW"));
+    }
+}
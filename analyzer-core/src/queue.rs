@@ -0,0 +1,69 @@
+use std::fmt;
+
+use serde_derive::{Deserialize, Serialize};
+
+/// The matchmaking queues a profile can track. Anything Riot runs that isn't listed here can still
+/// be asked for by id through `Custom`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum QueueType {
+    #[default]
+    RankedSolo,
+    RankedFlex,
+    NormalDraft,
+    Clash,
+    Aram,
+    Custom(i64),
+}
+
+impl QueueType {
+    /// Every named queue, in the order they're offered in the UI.
+    pub const ALL: [QueueType; 5] = [
+        QueueType::RankedSolo,
+        QueueType::RankedFlex,
+        QueueType::NormalDraft,
+        QueueType::Clash,
+        QueueType::Aram,
+    ];
+
+    /// Riot's ``queueId`` for this queue.
+    pub fn id(&self) -> i64 {
+        match self {
+            QueueType::RankedSolo => 420,
+            QueueType::RankedFlex => 440,
+            QueueType::NormalDraft => 400,
+            QueueType::Clash => 700,
+            QueueType::Aram => 450,
+            QueueType::Custom(id) => *id,
+        }
+    }
+
+    pub fn from_id(id: i64) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|q| q.id() == id)
+            .unwrap_or(QueueType::Custom(id))
+    }
+
+    /// Whether a game played in this queue with the given ``gameMode`` is one we can analyze.
+    /// Custom queues accept either map.
+    pub fn accepts_mode(&self, game_mode: &str) -> bool {
+        match self {
+            QueueType::Aram => game_mode == "ARAM",
+            QueueType::Custom(_) => game_mode == "CLASSIC" || game_mode == "ARAM",
+            _ => game_mode == "CLASSIC",
+        }
+    }
+}
+
+impl fmt::Display for QueueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueueType::RankedSolo => write!(f, "Ranked Solo/Duo"),
+            QueueType::RankedFlex => write!(f, "Ranked Flex"),
+            QueueType::NormalDraft => write!(f, "Normal Draft"),
+            QueueType::Clash => write!(f, "Clash"),
+            QueueType::Aram => write!(f, "ARAM"),
+            QueueType::Custom(id) => write!(f, "Queue {id}"),
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer};
+use serde_derive::Serialize;
+
+use crate::api_error::ApiError;
+
+/// Every shard Riot's public API serves, named by the short code players know it by. Each one
+/// knows its platform host (summoner-v4, league-v4) and its regional host (account-v1, match-v5).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[allow(clippy::allow_attributes, clippy::upper_case_acronyms)]
+pub enum Region {
+    #[default]
+    NA,
+    BR,
+    LAN,
+    LAS,
+    EUW,
+    EUNE,
+    TR,
+    RU,
+    ME,
+    KR,
+    JP,
+    OCE,
+    SG,
+    TW,
+    VN,
+}
+
+impl Region {
+    /// Every region, in the order they're offered in the UI.
+    pub const ALL: [Region; 15] = [
+        Region::NA,
+        Region::BR,
+        Region::LAN,
+        Region::LAS,
+        Region::EUW,
+        Region::EUNE,
+        Region::TR,
+        Region::RU,
+        Region::ME,
+        Region::KR,
+        Region::JP,
+        Region::OCE,
+        Region::SG,
+        Region::TW,
+        Region::VN,
+    ];
+
+    /// Platform routing value, e.g. ``na1``.
+    pub fn platform(&self) -> &'static str {
+        match self {
+            Region::NA => "na1",
+            Region::BR => "br1",
+            Region::LAN => "la1",
+            Region::LAS => "la2",
+            Region::EUW => "euw1",
+            Region::EUNE => "eun1",
+            Region::TR => "tr1",
+            Region::RU => "ru",
+            Region::ME => "me1",
+            Region::KR => "kr",
+            Region::JP => "jp1",
+            Region::OCE => "oc1",
+            Region::SG => "sg2",
+            Region::TW => "tw2",
+            Region::VN => "vn2",
+        }
+    }
+
+    /// Regional routing value used by match-v5.
+    pub fn regional(&self) -> &'static str {
+        match self {
+            Region::NA | Region::BR | Region::LAN | Region::LAS => "americas",
+            Region::EUW | Region::EUNE | Region::TR | Region::RU | Region::ME => "europe",
+            Region::KR | Region::JP => "asia",
+            Region::OCE | Region::SG | Region::TW | Region::VN => "sea",
+        }
+    }
+
+    /// Regional routing value for account-v1, which isn't served from ``sea``. Any cluster can
+    /// look up any account, so SEA players go to the closest one that answers.
+    pub fn account_regional(&self) -> &'static str {
+        match self.regional() {
+            "sea" => "asia",
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// Accepts either the short code (``EUW``) or the platform id (``euw1``), in any case.
+impl FromStr for Region {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        Self::ALL
+            .into_iter()
+            .find(|r| r.to_string().eq_ignore_ascii_case(s) || r.platform().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ApiError::invalid_input(&format!("Unknown region {s}")))
+    }
+}
+
+/// Reads anything [`Region::from_str`] does. Codes it doesn't know, such as ``CN`` and ``NONE``
+/// from profiles saved when the region picker still offered them, fall back to the default so
+/// those saves still load. A profile's own region is read by
+/// [`PlayerIdent`](crate::player::PlayerIdent) instead, which keeps such a code so the profile
+/// isn't quietly refreshed against the wrong shard.
+impl<'de> Deserialize<'de> for Region {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Ok(code.parse().unwrap_or_default())
+    }
+}
@@ -0,0 +1,211 @@
+use std::fmt;
+use std::str::FromStr;
+
+use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::api_error::ApiError;
+use crate::region::Region;
+
+/// A player's Riot ID, ``GameName#TAG``, together with the shard they play on. Displays (and
+/// parses back) as ``GameName#TAG#NA``, which is also how profiles are keyed on disk.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RiotId {
+    pub game_name: String,
+    pub tag_line: String,
+    pub region: Region,
+}
+
+impl RiotId {
+    /// Riot allows 3 to 16 characters before the ``#``...
+    pub const GAME_NAME_LEN: (usize, usize) = (3, 16);
+    /// ...and 3 to 5 after it.
+    pub const TAG_LINE_LEN: (usize, usize) = (3, 5);
+
+    /// Checks both halves against Riot's rules. Surrounding whitespace is dropped, and a leading
+    /// ``#`` on the tag is tolerated.
+    pub fn new(game_name: &str, tag_line: &str, region: Region) -> Result<Self, ApiError> {
+        let game_name = game_name.trim();
+        let tag_line = tag_line.trim().trim_start_matches('#');
+        Self::check("Game name", game_name, Self::GAME_NAME_LEN, |c| {
+            c.is_alphanumeric() || c == ' ' || c == '_' || c == '.'
+        })?;
+        Self::check("Tag", tag_line, Self::TAG_LINE_LEN, char::is_alphanumeric)?;
+        Ok(Self {
+            game_name: game_name.to_owned(),
+            tag_line: tag_line.to_owned(),
+            region,
+        })
+    }
+
+    /// Reads ``GameName#TAG`` as typed into a search box, for a region picked separately.
+    pub fn parse(input: &str, region: Region) -> Result<Self, ApiError> {
+        let Some((game_name, tag_line)) = input.trim().split_once('#') else {
+            return Err(ApiError::invalid_input(
+                "Riot IDs need their tag, e.g. Name#NA1",
+            ));
+        };
+        if tag_line.contains('#') {
+            return Err(ApiError::invalid_input(
+                "A Riot ID has a single # between the name and the tag",
+            ));
+        }
+        Self::new(game_name, tag_line, region)
+    }
+
+    /// ``GameName#TAG``, without the region.
+    pub fn name_tag(&self) -> String {
+        format!("{}#{}", self.game_name, self.tag_line)
+    }
+
+    /// Both halves escaped for use as URL path segments.
+    pub fn encoded(&self) -> (String, String) {
+        (
+            utf8_percent_encode(&self.game_name, NON_ALPHANUMERIC).to_string(),
+            utf8_percent_encode(&self.tag_line, NON_ALPHANUMERIC).to_string(),
+        )
+    }
+
+    /// Whether `other` names the same account. Riot IDs are case-insensitive.
+    pub fn same_player(&self, other: &RiotId) -> bool {
+        self.region == other.region
+            && self.game_name.to_lowercase() == other.game_name.to_lowercase()
+            && self.tag_line.to_lowercase() == other.tag_line.to_lowercase()
+    }
+
+    fn check(
+        what: &str,
+        value: &str,
+        (min, max): (usize, usize),
+        allowed: impl Fn(char) -> bool,
+    ) -> Result<(), ApiError> {
+        let len = value.chars().count();
+        if len < min || len > max {
+            return Err(ApiError::invalid_input(&format!(
+                "{what} must be {min} to {max} characters long, not {len}"
+            )));
+        }
+        if let Some(c) = value.chars().find(|c| !allowed(*c)) {
+            return Err(ApiError::invalid_input(&format!(
+                "{what} can't contain '{c}'"
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for RiotId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}#{}#{}", self.game_name, self.tag_line, self.region)
+    }
+}
+
+/// The inverse of [`Display`](fmt::Display): ``GameName#TAG#REGION``.
+impl FromStr for RiotId {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((name_tag, region)) = s.trim().rsplit_once('#') else {
+            return Err(ApiError::invalid_input(&format!(
+                "{s} isn't of the form Name#TAG#REGION"
+            )));
+        };
+        Self::parse(name_tag, region.parse()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_error::ErrorKind;
+
+    #[test]
+    fn parses_and_validates() {
+        // What was typed, and the name and tag it should come out as if it's valid.
+        let cases: [(&str, Option<(&str, &str)>); 15] = [
+            ("Jade#NA1", Some(("Jade", "NA1"))),
+            ("  Jade # NA1 ", Some(("Jade", "NA1"))),
+            ("Mr. Big_Cat#EUW", Some(("Mr. Big_Cat", "EUW"))),
+            ("Ñandú#Ñ12", Some(("Ñandú", "Ñ12"))),
+            ("Abc#123", Some(("Abc", "123"))),
+            (
+                "SixteenCharsLong#12345",
+                Some(("SixteenCharsLong", "12345")),
+            ),
+            ("Jade", None),
+            ("Jade#", None),
+            ("Jade#NA1#EUW", None),
+            ("Jo#NA1", None),
+            ("SeventeenCharsLon#NA1", None),
+            ("Jade#N1", None),
+            ("Jade#NA1234", None),
+            ("Ja-de#NA1", None),
+            ("Jade#NA_1", None),
+        ];
+        for (input, expected) in cases {
+            let parsed = RiotId::parse(input, Region::NA);
+            match expected {
+                Some((game_name, tag_line)) => {
+                    let id = parsed.unwrap_or_else(|e| panic!("{input:?}: {e}"));
+                    assert_eq!(
+                        (id.game_name.as_str(), id.tag_line.as_str()),
+                        (game_name, tag_line)
+                    );
+                }
+                None => {
+                    let error = parsed.expect_err(input);
+                    assert_eq!(error.kind(), ErrorKind::InvalidInput, "{input:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reads_the_region_suffix() {
+        let cases: [(&str, Option<Region>); 5] = [
+            ("Jade#NA1#EUW", Some(Region::EUW)),
+            ("Jade#NA1#euw1", Some(Region::EUW)),
+            ("Jade#NA1#kr ", Some(Region::KR)),
+            ("Jade#NA1#XX", None),
+            ("Jade#NA1", None),
+        ];
+        for (input, expected) in cases {
+            let parsed = input.parse::<RiotId>().ok().map(|id| id.region);
+            assert_eq!(parsed, expected, "{input:?}");
+        }
+        let id = RiotId::new("Mr. Big_Cat", "EUW", Region::EUW).unwrap();
+        assert_eq!(id.to_string().parse::<RiotId>().unwrap(), id);
+    }
+
+    #[test]
+    fn encodes_for_url_paths() {
+        let cases = [
+            (("Jade", "NA1"), ("Jade", "NA1")),
+            (("Big Cat", "NA1"), ("Big%20Cat", "NA1")),
+            (("Mr.X", "EUW"), ("Mr%2EX", "EUW")),
+            (("Ñandú", "Ñ12"), ("%C3%91and%C3%BA", "%C3%9112")),
+        ];
+        for ((game_name, tag_line), (name_enc, tag_enc)) in cases {
+            let id = RiotId::new(game_name, tag_line, Region::NA).unwrap();
+            assert_eq!(id.encoded(), (name_enc.to_owned(), tag_enc.to_owned()));
+        }
+    }
+
+    #[test]
+    fn matches_the_same_player_in_any_case() {
+        let id = |game_name: &str, tag_line: &str, region: Region| {
+            RiotId::new(game_name, tag_line, region).unwrap()
+        };
+        let jade = id("Jade", "NA1", Region::NA);
+        let cases = [
+            (id("Jade", "NA1", Region::NA), true),
+            (id("jADE", "na1", Region::NA), true),
+            (id("Jade", "NA2", Region::NA), false),
+            (id("Jaded", "NA1", Region::NA), false),
+            (id("Jade", "NA1", Region::EUW), false),
+        ];
+        for (other, same) in cases {
+            assert_eq!(jade.same_player(&other), same, "{other}");
+        }
+    }
+}
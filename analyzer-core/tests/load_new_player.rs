@@ -0,0 +1,52 @@
+//! Loads a brand new player end to end against the recorded responses in
+//! ``tests/fixtures/load_new_player``: one complete game surrendered at 12 minutes, and one whose
+//! timeline Riot no longer has.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use analyzer_core::data_processor::{Position, Side};
+use analyzer_core::interface::Interface;
+use analyzer_core::interface::transport::FixtureTransport;
+use analyzer_core::player::Player;
+use analyzer_core::region::Region;
+use analyzer_core::riot_id::RiotId;
+
+fn fixture_interface() -> Interface {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/load_new_player");
+    Interface::new("fixture-key").with_transport(Arc::new(FixtureTransport::new(root)))
+}
+
+#[test]
+fn loads_a_new_player_from_fixtures() {
+    let id = RiotId::new("Jade", "NA1", Region::NA).unwrap();
+    let mut player = Player::with_interface(&id, fixture_interface()).unwrap();
+    assert_eq!(player.ident.game_name, "Jade");
+    assert_eq!(player.start_data.puuid, "fixture-puuid-3");
+    assert_eq!(player.ident.summoner.summoner_level, 212);
+
+    player.load_new_player().unwrap();
+
+    // The game without a timeline is gone for good, so it isn't queued for another try.
+    assert!(player.pending_ids.is_empty());
+    assert_eq!(player.rank_error, None);
+    assert_eq!(player.games.games.len(), 1);
+
+    let raw = &player.games.games[0].raw_data;
+    assert_eq!(raw.match_id, "NA1_1001");
+    assert_eq!(raw.me.pos, Position::MIDDLE);
+    assert_eq!(raw.me.side, Side::BLUE);
+    assert_eq!(raw.me.champ, "Ahri");
+    assert_eq!(raw.opponent_champ(), "Syndra");
+
+    // Only the 10 minute checkpoint was reached before the surrender.
+    let reached: Vec<_> = raw
+        .checkpoints
+        .iter()
+        .map(|c| (c.minute, c.lanes.is_some()))
+        .collect();
+    assert_eq!(reached, [(10, true), (15, false), (20, false)]);
+    let mid = raw.checkpoints[0].lanes.unwrap()[2];
+    assert_eq!(mid, (510, 310));
+    assert_eq!(raw.checkpoints[0].cs.unwrap()[2], (80, 70));
+}
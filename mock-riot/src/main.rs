@@ -0,0 +1,214 @@
+//! A stand-in for Riot's API that answers from a fixture directory, for demos and for poking at
+//! the failure modes of the real thing. Point the app at it with ``JADE_API_BASE=http://127.0.0.1:8787``.
+//!
+//! ```text
+//! mock-riot --fixtures fixtures/ --port 8787 --latency-ms 150 --rate-limit-every 25 --error-every 40
+//! ```
+//!
+//! Fixtures use the same layout as `FixtureTransport`: a request for
+//! ``/lol/match/v5/matches/NA1_123/timeline`` is answered with
+//! ``{fixtures}/lol/match/v5/matches/NA1_123/timeline.json``. Leaving a timeline out is how a
+//! missing timeline is reproduced; it comes back as a 404 just like Riot's.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use analyzer_core::interface::transport::{FixtureTransport, Response, Transport};
+
+/// Limits advertised on every response, the same as a personal development key's.
+const APP_RATE_LIMIT: &str = "20:1,100:120";
+
+struct Config {
+    port: u16,
+    fixtures: PathBuf,
+    latency: Duration,
+    /// Every nth request is refused with a 429. Zero turns it off.
+    rate_limit_every: u64,
+    /// Every nth request fails with a 503. Zero turns it off.
+    error_every: u64,
+    /// Seconds sent in ``Retry-After`` with an injected 429.
+    retry_after: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            port: 8787,
+            fixtures: PathBuf::from("fixtures"),
+            latency: Duration::ZERO,
+            rate_limit_every: 0,
+            error_every: 0,
+            retry_after: 1,
+        }
+    }
+}
+
+impl Config {
+    fn from_args() -> Result<Self, String> {
+        let mut config = Config::default();
+        let mut args = std::env::args().skip(1);
+        while let Some(flag) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("{flag} needs a value"));
+            let number = |v: String| {
+                v.parse::<u64>()
+                    .map_err(|_| format!("{flag} expects a number, got {v}"))
+            };
+            match flag.as_str() {
+                "--port" => {
+                    config.port = value()?
+                        .parse()
+                        .map_err(|_| String::from("--port expects a port number"))?;
+                }
+                "--fixtures" => config.fixtures = PathBuf::from(value()?),
+                "--latency-ms" => config.latency = Duration::from_millis(number(value()?)?),
+                "--rate-limit-every" => config.rate_limit_every = number(value()?)?,
+                "--error-every" => config.error_every = number(value()?)?,
+                "--retry-after" => config.retry_after = number(value()?)?,
+                "--help" | "-h" => return Err(usage()),
+                other => return Err(format!("unknown flag {other}\n\n{}", usage())),
+            }
+        }
+        Ok(config)
+    }
+}
+
+fn usage() -> String {
+    String::from(
+        "usage: mock-riot [--port N] [--fixtures DIR] [--latency-ms N] \
+         [--rate-limit-every N] [--error-every N] [--retry-after SECS]",
+    )
+}
+
+struct Server {
+    config: Config,
+    fixtures: FixtureTransport,
+    requests: AtomicU64,
+}
+
+impl Server {
+    fn respond(&self, path: &str) -> Response {
+        let n = self.requests.fetch_add(1, Ordering::SeqCst) + 1;
+        std::thread::sleep(self.config.latency);
+
+        let every = |interval: u64| interval != 0 && n.is_multiple_of(interval);
+        let mut resp = if every(self.config.rate_limit_every) {
+            let mut resp = Self::status(429);
+            resp.headers.insert(
+                String::from("retry-after"),
+                self.config.retry_after.to_string(),
+            );
+            resp.headers.insert(
+                String::from("x-rate-limit-type"),
+                String::from("application"),
+            );
+            resp
+        } else if every(self.config.error_every) {
+            Self::status(503)
+        } else if path.starts_with("/lol/status/v4/platform-data") {
+            // Only ever used to check a key, which the mock accepts whatever it is.
+            Response {
+                status: 200,
+                body: String::from("{}"),
+                ..Default::default()
+            }
+        } else {
+            self.fixtures
+                .get("", path, "")
+                .unwrap_or_else(|_| Self::status(500))
+        };
+        resp.headers
+            .insert(String::from("x-app-rate-limit"), APP_RATE_LIMIT.to_owned());
+        println!("{n:>5} {} {path}", resp.status);
+        resp
+    }
+
+    /// A bodyless error in the shape Riot sends them.
+    fn status(status: u16) -> Response {
+        Response {
+            status,
+            body: format!(r#"{{"status":{{"status_code":{status}}}}}"#),
+            ..Default::default()
+        }
+    }
+
+    fn handle(&self, stream: TcpStream) -> std::io::Result<()> {
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        // Headers aren't looked at, but have to be read past before answering.
+        let mut header = String::new();
+        while reader.read_line(&mut header)? > 2 {
+            header.clear();
+        }
+
+        let mut parts = request_line.split_whitespace();
+        let resp = match (parts.next(), parts.next()) {
+            (Some("GET"), Some(path)) => self.respond(path),
+            _ => Self::status(405),
+        };
+        write_response(stream, &resp)
+    }
+}
+
+fn write_response(mut stream: TcpStream, resp: &Response) -> std::io::Result<()> {
+    let reason = match resp.status {
+        200 => "OK",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        429 => "Too Many Requests",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    };
+    let mut head = format!(
+        "HTTP/1.1 {} {reason}\r\nContent-Type: application/json;charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n",
+        resp.status,
+        resp.body.len()
+    );
+    for (name, value) in &resp.headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(resp.body.as_bytes())?;
+    stream.flush()
+}
+
+fn main() {
+    let config = match Config::from_args() {
+        Ok(config) => config,
+        Err(msg) => {
+            eprintln!("{msg}");
+            std::process::exit(2);
+        }
+    };
+    let listener = match TcpListener::bind(("127.0.0.1", config.port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("could not listen on port {}: {e}", config.port);
+            std::process::exit(1);
+        }
+    };
+    println!(
+        "serving {} on http://127.0.0.1:{}",
+        config.fixtures.display(),
+        config.port
+    );
+
+    let server = Arc::new(Server {
+        fixtures: FixtureTransport::new(&config.fixtures),
+        config,
+        requests: AtomicU64::new(0),
+    });
+    for stream in listener.incoming().flatten() {
+        let server = Arc::clone(&server);
+        std::thread::spawn(move || {
+            if let Err(e) = server.handle(stream) {
+                eprintln!("connection error: {e}");
+            }
+        });
+    }
+}
@@ -1,54 +1,71 @@
-use std::error::Error;
-use std::fmt::{self, Formatter};
+use std::io;
+use std::sync::Arc;
 
-#[derive(Debug, Clone)]
-pub struct AppError {
-    pub details: String,
+use analyzer_core::api_error::{ApiError, ErrorKind};
+use thiserror::Error;
+
+/// Anything that can go wrong while the UI is loading or saving a profile. The sources sit behind
+/// `Arc` so the error can be cloned into the app state and shown every frame.
+#[derive(Debug, Clone, Error)]
+pub enum AppError {
+    #[error(transparent)]
+    Api(Arc<ApiError>),
+
+    #[error(transparent)]
+    Io(Arc<io::Error>),
+
+    #[error("saved profile could not be read: {0}")]
+    Save(Arc<serde_json::Error>),
+
+    #[error("{0}")]
+    Message(String),
 }
 
-#[allow(clippy::allow_attributes, clippy::todo)]
 impl AppError {
     pub fn new(msg: &str) -> Self {
-        Self {
-            details: msg.to_owned(),
-        }
+        Self::Message(msg.to_owned())
     }
-}
 
-#[allow(clippy::allow_attributes, clippy::todo)]
-impl fmt::Display for AppError {
-    fn fmt(&self, _f: &mut Formatter<'_>) -> fmt::Result {
-        todo!()
-    }
-}
-
-#[allow(clippy::allow_attributes, clippy::todo)]
-impl Error for AppError {
-    fn description(&self) -> &str {
-        todo!()
+    /// A line telling the user what they can do about the error, if there's anything.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            Self::Api(e) => match e.kind() {
+                ErrorKind::NotFound => Some("Check the Riot ID and the selected region."),
+                ErrorKind::Unauthorized => Some("No API key is set."),
+                ErrorKind::Forbidden => {
+                    Some("The API key has expired or is invalid. Generate a new one.")
+                }
+                ErrorKind::RateLimited => Some("Too many requests. Wait a minute and try again."),
+                ErrorKind::ServerError => {
+                    Some("Riot's servers are having trouble. Try again later.")
+                }
+                ErrorKind::Network => Some("Could not reach Riot. Check your connection."),
+                ErrorKind::Schema => Some("Riot sent data this version doesn't understand."),
+                ErrorKind::Storage => Some("A saved file is unreadable or could not be written."),
+                ErrorKind::InvalidInput => Some("Enter the player as Name#Tag."),
+                ErrorKind::UnexpectedStatus | ErrorKind::Other => None,
+            },
+            Self::Io(_) => Some("A saved file could not be read or written."),
+            Self::Save(_) => Some("The profile file may be corrupt. Remove it and add it again."),
+            Self::Message(_) => None,
+        }
     }
 }
 
-impl From<std::io::Error> for AppError {
-    fn from(e: std::io::Error) -> Self {
-        Self {
-            details: e.to_string(),
-        }
+impl From<io::Error> for AppError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(Arc::new(e))
     }
 }
 
 impl From<serde_json::Error> for AppError {
     fn from(e: serde_json::Error) -> Self {
-        Self {
-            details: e.to_string(),
-        }
+        Self::Save(Arc::new(e))
     }
 }
 
-impl From<analyzer_core::api_error::ApiError> for AppError {
-    fn from(e: analyzer_core::api_error::ApiError) -> Self {
-        Self {
-            details: e.to_string(),
-        }
+impl From<ApiError> for AppError {
+    fn from(e: ApiError) -> Self {
+        Self::Api(Arc::new(e))
     }
 }
@@ -0,0 +1,147 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use analyzer_core::interface::api_key::{ApiKey, KeyStatus};
+use serde::{Deserialize, Serialize};
+
+use crate::app::app_error::AppError;
+use crate::ui::PlayerLoadCtx;
+
+/// Where the key currently in use came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeySource {
+    /// ``assets/config.json``
+    Config,
+    /// ``API_TOKEN``, from the environment or a ``.env`` file.
+    Environment,
+    /// Typed into the settings window this session.
+    Entered,
+    None,
+}
+
+/// The latest key check: the key it's for, and its result once it's come back.
+#[derive(Default)]
+struct Check {
+    key: String,
+    result: Option<Result<KeyStatus, AppError>>,
+}
+
+type Validation = Arc<Mutex<Check>>;
+
+#[derive(Serialize, Deserialize, Default)]
+struct Config {
+    #[serde(default)]
+    api_key: String,
+}
+
+/// Owns the Riot API key for the whole app. The key lives in [`ApiKey::shared`], so every
+/// interface the loader hands out, and every player already loaded, follows it when it changes.
+pub struct KeyStore {
+    key: ApiKey,
+    source: KeySource,
+    config_path: PathBuf,
+    validation: Validation,
+}
+
+impl KeyStore {
+    /// Picks up a key from ``assets/config.json`` if one was saved there, otherwise from
+    /// ``API_TOKEN``. With neither the store starts empty and the user is asked for one.
+    pub fn load(root: &Path) -> Self {
+        let config_path = root.join("assets/config.json");
+        let key = ApiKey::shared();
+        let saved = fs::read_to_string(&config_path)
+            .ok()
+            .and_then(|s| serde_json::from_str::<Config>(&s).ok())
+            .map(|c| c.api_key)
+            .filter(|k| !k.trim().is_empty());
+
+        let source = if let Some(saved) = saved {
+            key.set(&saved);
+            KeySource::Config
+        } else {
+            dotenv::dotenv().ok();
+            match std::env::var("API_TOKEN") {
+                Ok(env_key) if !env_key.trim().is_empty() => {
+                    key.set(&env_key);
+                    KeySource::Environment
+                }
+                _ => KeySource::None,
+            }
+        };
+
+        Self {
+            key,
+            source,
+            config_path,
+            validation: Arc::default(),
+        }
+    }
+
+    pub fn key(&self) -> ApiKey {
+        self.key.clone()
+    }
+
+    pub fn source(&self) -> KeySource {
+        self.source
+    }
+
+    /// Swaps in a new key, checks it and saves it to ``assets/config.json``. The key is in use
+    /// and being checked even if saving it fails.
+    pub fn set(&mut self, key: &str) -> Result<(), AppError> {
+        self.key.set(key);
+        self.source = KeySource::Entered;
+        self.validate();
+        self.save()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save(&self) -> Result<(), AppError> {
+        let config = Config {
+            api_key: self.key.get(),
+        };
+        fs::write(&self.config_path, serde_json::to_string_pretty(&config)?)?;
+        Ok(())
+    }
+
+    /// There's no config file in the browser; an entered key lasts the session.
+    #[cfg(target_arch = "wasm32")]
+    fn save(&self) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    /// What the last check made of the key. `None` while one is still running.
+    pub fn status(&self) -> Option<Result<KeyStatus, AppError>> {
+        self.validation
+            .lock()
+            .expect("Failed mutex guard check")
+            .result
+            .clone()
+    }
+
+    /// Checks the current key against Riot in the background; see [`KeyStore::status`]. A
+    /// check that finishes after the key has changed again is thrown away, so an old key's
+    /// result never shows against a new one.
+    pub fn validate(&self) {
+        let key = self.key.get();
+        *self.validation.lock().expect("Failed mutex operation") = Check {
+            key: key.clone(),
+            result: None,
+        };
+        // Checked with a copy, so swapping the shared key mid-check can't change what's tested.
+        let interface = PlayerLoadCtx::base_interface(ApiKey::new(&key));
+        let validation = Arc::clone(&self.validation);
+        let check = move || async move {
+            let result = interface.validate_key_async().await.map_err(AppError::from);
+            let mut latest = validation.lock().expect("Failed mutex operation");
+            if latest.key == key {
+                latest.result = Some(result);
+            }
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        std::thread::spawn(move || pollster::block_on(check()));
+        #[cfg(target_arch = "wasm32")]
+        wasm_bindgen_futures::spawn_local(check());
+    }
+}
@@ -1,3 +1,4 @@
 pub mod app_error;
 pub mod home;
+pub mod key_store;
 pub mod ui;
@@ -2,18 +2,29 @@ use std::fmt;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
+use analyzer_core::interface::api_key::ApiKey;
 use analyzer_core::player::Player;
+use analyzer_core::queue::QueueType;
+use analyzer_core::region::Region;
+use analyzer_core::riot_id::RiotId;
 use eframe::egui::CentralPanel;
 use egui::Context;
 use egui::TextStyle;
 use egui::{FontFamily, FontId};
 
 use crate::app::app_error::AppError;
+use crate::app::key_store::KeyStore;
+use crate::ui::game_chart::GameChart;
+use crate::ui::import::ImportForm;
+use crate::ui::player_interface::IndexEntry;
 pub mod error_window;
+pub mod game_chart;
 pub mod home;
+pub mod import;
 pub mod loading;
 pub mod player_interface;
 pub mod profiles_list;
+pub mod settings;
 pub mod stats_display;
 pub mod stats_page;
 
@@ -26,7 +37,20 @@ pub struct App {
 
     ///Server Region selected by the player. When passed to the backend, this is tacked onto the
     ///username with another #. Ex: WhaleMilk#PHUD#NA
-    region: Regions,
+    region: Region,
+
+    /// Queues a newly searched profile imports games from. Picked on the home screen.
+    queues: Vec<QueueType>,
+
+    /// Minutes a newly searched profile compares lane opponents at, as typed on the home screen,
+    /// e.g. ``10, 15, 20``.
+    checkpoint_input: String,
+
+    /// Checkpoint minute the stats page's GD graph is showing.
+    checkpoint: u32,
+
+    /// Queue the stats page is currently showing. ``None`` shows every queue the profile tracks.
+    queue_filter: Option<QueueType>,
 
     /// The state of the UI. Tracks what is displayed when, mostly in central plane. See State
     /// enumu
@@ -41,9 +65,9 @@ pub struct App {
     ///once. Boolean is mostly for redrawing purposes.
     has_loaded: bool,
 
-    ///A list of players stored in ``./assets/indexed_players.json``. This is an effective list of
-    ///players who's full profile json files are saved to disk in ``./assets/players/``
-    indexed_players: Vec<String>,
+    ///A list of players stored in ``./assets/profile_index.json``. This is an effective list of
+    ///players who's full profile json files are saved to disk in ``./assets/profiles/``
+    indexed_players: Vec<IndexEntry>,
 
     ///A boolean to check if the program needs to re-read ``./assets/indexed_players.json`` due to an
     ///update of some kind.
@@ -62,9 +86,28 @@ pub struct App {
     /// set, the program spawns a thread for async loading of player from the backend.
     loading_started: bool,
 
+    /// Set when the stats page asks for new games. The next load refreshes ``loaded_player``
+    /// instead of looking the player up from scratch.
+    refresh: bool,
+
     /// Path to the root of project directory for finding assets folder easily.
     root_dir: PathBuf,
 
+    /// The Riot API key, where it came from and whether Riot still accepts it.
+    keys: KeyStore,
+
+    /// Whether the settings window is open.
+    show_settings: bool,
+
+    /// What's been typed into the settings window's key field and not yet saved.
+    key_input: String,
+
+    /// The import window and what's been typed into it.
+    import: ImportForm,
+
+    /// The single-game chart window and what it's showing.
+    game_chart: GameChart,
+
     ///Tracks possible errors from either the front end itself or from the backend.
     err: Option<AppError>, // Error field that tracks if there is an error thrown by player interface
 }
@@ -82,10 +125,14 @@ pub enum LoadingState<T> {
 /// &self to somewhere else while inside a closure.
 #[derive(Clone)]
 pub struct PlayerLoadCtx {
-    pub username: String,
-    pub region: Regions,
+    pub riot_id: RiotId,
+    pub queues: Vec<QueueType>,
+    pub checkpoints: Vec<u32>,
     pub root_dir: PathBuf,
-    pub indexed_players: Vec<String>,
+    pub indexed_players: Vec<IndexEntry>,
+    pub api_key: ApiKey,
+    /// An already loaded player to fetch new games for, in place of a fresh lookup.
+    pub refresh: Option<Player>,
 }
 
 /// Enum for describing window state. Each state has a different associated page for drawing on
@@ -101,47 +148,48 @@ enum State {
 }
 
 /// A description of different graph displays that can be displayed on the Stats Page.
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 #[allow(clippy::upper_case_acronyms, clippy::allow_attributes)]
 enum GraphType {
-    #[default]
-    GD15,
+    /// Gold difference at the given minute.
+    GD(u32),
+    XPD(u32),
+    CSD(u32),
+    LevelDiff(u32),
     CSM,
     DPM,
     KP,
-}
-
-/// The different available servers that can be pulled from
-#[derive(Debug, Default, PartialEq, Eq, Clone)]
-#[allow(clippy::upper_case_acronyms, clippy::allow_attributes)]
-pub enum Regions {
-    NA,
-    EUW,
-    EUNE,
-    KR,
-    CN,
-    #[default]
-    NONE,
+    LP,
 }
 
 impl fmt::Display for GraphType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
-            Self::GD15 => write!(f, "GD@15"),
+            Self::GD(minute) => write!(f, "GD@{minute}"),
+            Self::XPD(minute) => write!(f, "XPD@{minute}"),
+            Self::CSD(minute) => write!(f, "CSD@{minute}"),
+            Self::LevelDiff(minute) => write!(f, "Level diff@{minute}"),
             Self::CSM => write!(f, "CS/M"),
             Self::DPM => write!(f, "D/M"),
             Self::KP => write!(f, "KP%"),
+            Self::LP => write!(f, "LP"),
         }
     }
 }
 impl Default for App {
     fn default() -> Self {
         let dir = project_root::get_project_root().unwrap_or_default();
+        let keys = KeyStore::load(&dir);
+        keys.validate();
         Self {
             username: String::default(),
-            region: Regions::NA,
+            region: Region::NA,
+            queues: vec![QueueType::RankedSolo],
+            checkpoint_input: String::from("10, 15, 20"),
+            checkpoint: 15,
+            queue_filter: None,
             state: State::Home,
-            graph_dimensions: (2, 2),
+            graph_dimensions: (4, 2),
             has_loaded: false,
             indexed_players: PlayerLoadCtx::read_indexed_players(&dir)
                 .expect("Could not read indexed players on initialization"),
@@ -149,7 +197,13 @@ impl Default for App {
             loaded_player: Player::default(),
             player: Arc::new(Mutex::new(LoadingState::Dormant)),
             loading_started: false,
+            refresh: false,
             root_dir: dir,
+            keys,
+            show_settings: false,
+            key_input: String::new(),
+            import: ImportForm::default(),
+            game_chart: GameChart::default(),
             err: None,
         }
     }
@@ -166,6 +220,15 @@ impl App {
     fn ui(&mut self, ctx: &egui::Context) {
         set_style(ctx);
         self.top_bar(ctx);
+        if self.show_settings {
+            self.display_settings(ctx);
+        }
+        if self.import.open {
+            self.display_import(ctx);
+        }
+        if self.game_chart.open {
+            self.display_game_chart(ctx);
+        }
         self.draw_side_panel(ctx);
         self.draw_central_panel(ctx);
         ctx.request_repaint();
@@ -1,9 +1,10 @@
+use crate::app::app_error::AppError;
 use crate::ui::App;
 use egui::{Align2, Color32, Frame};
 use egui::{Context, RichText, Vec2, Window};
 
 impl App {
-    pub fn display_error_window(&mut self, ctx: &Context, details: &String) {
+    pub fn display_error_window(&mut self, ctx: &Context, error: &AppError) {
         let mut frame = Frame::window(&ctx.style());
         frame.fill = Color32::from_rgba_unmultiplied(100, 0, 0, 75);
         frame.shadow = egui::Shadow::NONE;
@@ -15,7 +16,10 @@ impl App {
             .frame(frame)
             .fade_in(true)
             .show(ctx, |ui| {
-                ui.label(RichText::new(format!("There was an error: {details}")).small());
+                ui.label(RichText::new(format!("There was an error: {error}")).small());
+                if let Some(hint) = error.hint() {
+                    ui.label(RichText::new(hint).small());
+                }
             });
     }
 }
@@ -0,0 +1,100 @@
+use analyzer_core::data_processor::{Game, SeriesStat};
+use egui::{Context, RichText, Window};
+use egui_plot::{Legend, Line, Plot, PlotPoints};
+
+use crate::ui::App;
+
+/// Which game the single-game chart shows, and what of it.
+#[derive(Default)]
+pub struct GameChart {
+    pub open: bool,
+    /// Index into the loaded player's games.
+    game: usize,
+    stat: SeriesStat,
+}
+
+impl App {
+    /// Window plotting one game minute by minute: the player, their lane opponent, and the gap
+    /// between them, so it shows when a lead was built or thrown.
+    pub fn display_game_chart(&mut self, ctx: &Context) {
+        let mut open = self.game_chart.open;
+        Window::new("Game chart")
+            .open(&mut open)
+            .collapsible(false)
+            .default_size([520.0, 320.0])
+            .show(ctx, |ui| {
+                let games = &self.loaded_player.games.games;
+                let Some(game) = games.get(self.game_chart.game) else {
+                    ui.label("No games loaded");
+                    return;
+                };
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_salt("Chart_Game")
+                        .selected_text(game_label(self.game_chart.game, game))
+                        .show_ui(ui, |ui| {
+                            for (i, game) in games.iter().enumerate().rev() {
+                                ui.selectable_value(
+                                    &mut self.game_chart.game,
+                                    i,
+                                    game_label(i, game),
+                                );
+                            }
+                        });
+                    egui::ComboBox::from_id_salt("Chart_Stat")
+                        .selected_text(self.game_chart.stat.to_string())
+                        .show_ui(ui, |ui| {
+                            for stat in SeriesStat::ALL {
+                                ui.selectable_value(
+                                    &mut self.game_chart.stat,
+                                    stat,
+                                    stat.to_string(),
+                                );
+                            }
+                        });
+                });
+
+                let series = &game.raw_data.series;
+                if series.is_empty() {
+                    ui.label(
+                        RichText::new("This game was stored before minute-by-minute data was kept")
+                            .small(),
+                    );
+                    return;
+                }
+                let stat = self.game_chart.stat;
+                let minutes = series.minutes();
+                let points = |values: &[i32]| -> PlotPoints<'_> {
+                    minutes
+                        .iter()
+                        .zip(values)
+                        .map(|(x, y)| [*x, f64::from(*y)])
+                        .collect()
+                };
+                Plot::new("Game_Chart_Plot")
+                    .legend(Legend::default().position(egui_plot::Corner::LeftTop))
+                    .x_axis_label("Minute")
+                    .show(ui, |plot_ui| {
+                        plot_ui.line(Line::new("You", points(series.me.get(stat))));
+                        plot_ui.line(Line::new("Opponent", points(series.opponent.get(stat))));
+                        plot_ui.line(Line::new("Difference", points(&series.diff(stat))));
+                    });
+            });
+        self.game_chart.open = open;
+    }
+
+    /// Opens the chart on the most recent game.
+    pub fn open_game_chart(&mut self) {
+        self.game_chart.game = self.loaded_player.games.games.len().saturating_sub(1);
+        self.game_chart.open = true;
+    }
+}
+
+fn game_label(index: usize, game: &Game) -> String {
+    let result = if game.graph_data.wl { "Win" } else { "Loss" };
+    format!(
+        "{}. {} vs {} ({result})",
+        index + 1,
+        game.graph_data.champion,
+        game.raw_data.opponent_champ()
+    )
+}
@@ -1,5 +1,9 @@
-use crate::ui::{App, Regions, State};
+use std::sync::{Arc, Mutex};
+
+use crate::ui::{App, LoadingState, State};
 use analyzer_core::player::Player;
+use analyzer_core::queue::QueueType;
+use analyzer_core::region::Region;
 use egui::Context;
 use egui::TopBottomPanel;
 use egui::Ui;
@@ -10,8 +14,8 @@ impl App {
             ui.add_space(ui.available_height() / 2.5 - 80.0);
             ui.set_max_width(200.0);
             if let Some(e) = &self.err {
-                let details = e.details.clone();
-                self.display_error_window(ctx, &details);
+                let error = e.clone();
+                self.display_error_window(ctx, &error);
             }
 
             ui.label("Enter Username");
@@ -21,13 +25,11 @@ impl App {
                 //region selection box
                 egui::ComboBox::from_id_salt("Region_Box")
                     .width(50.0)
-                    .selected_text(format!("{:?}", self.region))
+                    .selected_text(self.region.to_string())
                     .show_ui(ui, |ui| {
-                        ui.selectable_value(&mut self.region, Regions::NA, "NA");
-                        ui.selectable_value(&mut self.region, Regions::EUW, "EUW");
-                        ui.selectable_value(&mut self.region, Regions::EUNE, "EUNE");
-                        ui.selectable_value(&mut self.region, Regions::KR, "KR");
-                        ui.selectable_value(&mut self.region, Regions::CN, "CN");
+                        for region in Region::ALL {
+                            ui.selectable_value(&mut self.region, region, region.to_string());
+                        }
                     });
 
                 if input_box.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
@@ -36,6 +38,20 @@ impl App {
                     self.state = State::Loading;
                 }
             });
+            self.key_status_label(ui);
+            ui.label("Queues to import");
+            for queue in QueueType::ALL {
+                let mut selected = self.queues.contains(&queue);
+                if ui.checkbox(&mut selected, queue.to_string()).changed() {
+                    if selected {
+                        self.queues.push(queue);
+                    } else {
+                        self.queues.retain(|q| q != &queue);
+                    }
+                }
+            }
+            ui.label("Compare with lane opponent at (minutes)");
+            ui.add(egui::TextEdit::singleline(&mut self.checkpoint_input).hint_text("10, 15, 20"));
             if ui.button("Go!").clicked() {
                 self.err = None;
                 self.loading_started = false;
@@ -54,13 +70,16 @@ impl App {
                         self.loading_started = false;
                     }
                     if self.state == State::Stats && ui.button("Reload player").clicked() {
-                        match self.loaded_player.load_new_games() {
-                            Ok(_) => {}
-                            Err(e) => {
-                                self.state = State::Home;
-                                self.err = Some(e.into());
-                            }
-                        }
+                        self.player = Arc::new(Mutex::new(LoadingState::Loading));
+                        self.refresh = true;
+                        self.loading_started = false;
+                        self.state = State::Loading;
+                    }
+                    if ui.button("Import games").clicked() {
+                        self.import.open = true;
+                    }
+                    if ui.button("Settings").clicked() {
+                        self.show_settings = true;
                     }
                     if ui.button("Exit").clicked() {
                         ctx.send_viewport_cmd(egui::ViewportCommand::Close);
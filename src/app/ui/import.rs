@@ -0,0 +1,104 @@
+use std::fs;
+use std::path::Path;
+
+use analyzer_core::data_processor::Checkpoint;
+use analyzer_core::import::{ImportTarget, import_dir};
+use analyzer_core::player::Player;
+use egui::{Context, RichText, TextEdit, Window};
+
+use crate::app::app_error::AppError;
+use crate::ui::{App, PlayerLoadCtx, State};
+
+/// What's been typed into the import window.
+#[derive(Default)]
+pub struct ImportForm {
+    pub open: bool,
+    dir: String,
+    target: String,
+    /// How the last import went.
+    status: Option<String>,
+    /// Where the last import's files strayed from the models, if they did.
+    drift: Option<String>,
+}
+
+impl App {
+    /// Window for reading match and timeline JSON from a folder into a profile, no API key
+    /// needed. Games land in the player's saved profile, which is created if there isn't one.
+    pub fn display_import(&mut self, ctx: &Context) {
+        let mut open = self.import.open;
+        Window::new("Import games")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label("Folder of match and timeline files");
+                ui.add(TextEdit::singleline(&mut self.import.dir).hint_text("exports/"));
+                ui.label(format!("Player (Riot ID on {}, or PUUID)", self.region));
+                ui.add(TextEdit::singleline(&mut self.import.target).hint_text("Name#TAG"));
+                if ui.button("Import").clicked() {
+                    self.import.status = Some(match self.import_games() {
+                        Ok(status) => status,
+                        Err(e) => e.to_string(),
+                    });
+                }
+                if let Some(status) = &self.import.status {
+                    let label = ui.label(RichText::new(status).small());
+                    if let Some(drift) = &self.import.drift {
+                        label.on_hover_text(drift);
+                    }
+                }
+            });
+        self.import.open = open;
+    }
+
+    /// Imports the folder in the form and shows the resulting profile. Games are compared at
+    /// the checkpoints picked on the home screen.
+    fn import_games(&mut self) -> Result<String, AppError> {
+        let target = ImportTarget::parse(&self.import.target, self.region)?;
+        let checkpoints = Checkpoint::parse_minutes(&self.checkpoint_input)?;
+        let dir = self.import.dir.trim();
+        let import = import_dir(Path::new(dir), &target, &checkpoints)?;
+        let Some(ident) = import.ident else {
+            return Err(AppError::new(&format!(
+                "No games for {target} found in {dir}"
+            )));
+        };
+
+        let profile_path = PlayerLoadCtx::profile_path(&self.root_dir, &ident.summoner.puuid);
+        let mut player = match fs::read_to_string(profile_path) {
+            Ok(saved) => {
+                let mut player = Player::default();
+                player.load_indexed_player(saved)?;
+                player
+            }
+            Err(_) => Player {
+                checkpoints,
+                ..Player::offline(ident)
+            },
+        };
+        let added = player.import_games(import.batch.games);
+        PlayerLoadCtx::save_profile(&self.root_dir, &player)?;
+
+        self.username = player.ident.riot_id().name_tag();
+        self.region = player.ident.server;
+        self.loaded_player = player;
+        self.queue_filter = None;
+        self.update_index_players = true;
+        self.state = State::Stats;
+
+        let failed = import.batch.failures.len();
+        let mut status = match failed {
+            0 => format!("Imported {added} new game(s)"),
+            _ => format!("Imported {added} new game(s); {failed} match(es) couldn't be read"),
+        };
+        self.import.drift = (!import.drift.is_empty()).then(|| import.drift.to_string());
+        if !import.drift.is_empty() {
+            status.push_str(&format!(
+                "\n{} field(s) were missing and {} weren't recognised; the models may need updating",
+                import.drift.missing.len(),
+                import.drift.unknown.len()
+            ));
+        }
+        Ok(status)
+    }
+}
@@ -1,4 +1,7 @@
-use egui::Ui;
+use analyzer_core::data_processor::Checkpoint;
+use analyzer_core::interface::rate_limit::{RateLimiter, Scope};
+use analyzer_core::riot_id::RiotId;
+use egui::{RichText, Ui};
 use std::sync::Arc;
 
 use crate::app::app_error::AppError;
@@ -14,24 +17,57 @@ impl App {
         if self.state == State::Loading && !self.loading_started {
             self.loading_started = true;
 
+            let refresh = std::mem::take(&mut self.refresh).then(|| self.loaded_player.clone());
+            let riot_id = match &refresh {
+                Some(player) => player.ident.riot_id(),
+                None => match RiotId::parse(&self.username, self.region) {
+                    Ok(id) => id,
+                    Err(e) => {
+                        self.err = Some(e.into());
+                        self.state = State::Home;
+                        return Ok(());
+                    }
+                },
+            };
+            let checkpoints = match &refresh {
+                Some(player) => player.checkpoints.clone(),
+                None => match Checkpoint::parse_minutes(&self.checkpoint_input) {
+                    Ok(minutes) => minutes,
+                    Err(e) => {
+                        self.err = Some(e.into());
+                        self.state = State::Home;
+                        return Ok(());
+                    }
+                },
+            };
             let mut ctx = PlayerLoadCtx {
-                username: self.username.clone(),
-                region: self.region.clone(),
+                riot_id,
+                queues: self.queues.clone(),
+                checkpoints,
                 root_dir: self.root_dir.clone(),
                 indexed_players: self.indexed_players.clone(),
+                api_key: self.keys.key(),
+                refresh,
             };
 
             let loading_player = Arc::clone(&self.player);
-
-            std::thread::spawn(move || {
-                let result = ctx.load_player();
+            // The future isn't Send, so it's only built once it's where it will run.
+            let load = move || async move {
+                let result = ctx.load_player().await;
 
                 let mut guard = loading_player.lock().expect("Failed mutex operation");
                 *guard = match result {
                     Ok(player) => LoadingState::Loaded(player),
                     Err(e) => LoadingState::Error(e),
                 };
-            });
+            };
+
+            // Natively the load gets a thread of its own to block on; in the browser it has to
+            // share the event loop with the UI.
+            #[cfg(not(target_arch = "wasm32"))]
+            std::thread::spawn(move || pollster::block_on(load()));
+            #[cfg(target_arch = "wasm32")]
+            wasm_bindgen_futures::spawn_local(load());
         }
         Ok(())
     }
@@ -45,11 +81,15 @@ impl App {
                         ui.heading("Loading...");
                         ui.spinner();
                     });
+                    Self::display_quota(ui);
                 });
             }
             LoadingState::Loaded(player) => {
                 self.update_index_players = true;
                 self.loaded_player = player.clone();
+                // Riot's spelling of the name, whatever case it was typed in.
+                self.username = player.ident.riot_id().name_tag();
+                self.queue_filter = None;
                 self.has_loaded = true;
                 self.state = State::Stats;
             }
@@ -59,4 +99,25 @@ impl App {
             }
         }
     }
+
+    /// Lists how much of the app-wide API budget is left on each routing host, so a slow load
+    /// can be told apart from a stuck one.
+    fn display_quota(ui: &mut Ui) {
+        for quota in RateLimiter::shared()
+            .usage()
+            .iter()
+            .filter(|q| q.scope == Scope::App)
+        {
+            ui.label(
+                RichText::new(format!(
+                    "{}: {}/{} requests left ({}s window)",
+                    quota.host,
+                    quota.remaining(),
+                    quota.limit,
+                    quota.window.as_secs()
+                ))
+                .small(),
+            );
+        }
+    }
 }
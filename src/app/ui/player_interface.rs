@@ -1,109 +1,263 @@
+use analyzer_core::interface::Interface;
+use analyzer_core::interface::api_key::ApiKey;
+#[cfg(not(target_arch = "wasm32"))]
+use analyzer_core::interface::cache::ResponseCache;
+use analyzer_core::interface::transport::FixtureTransport;
 use analyzer_core::player::Player;
-use dotenv::dotenv;
+use analyzer_core::riot_id::RiotId;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::fs::{File, OpenOptions};
 use std::io::Error;
-use std::io::prelude::*;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use crate::app::app_error::AppError;
 use crate::ui::PlayerLoadCtx;
 
+/// One saved profile. The file is keyed by PUUID, which stays put when the player changes their
+/// Riot ID; the Riot ID is only what the profile is listed and searched under.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct IndexEntry {
+    pub puuid: String,
+    pub riot_id: RiotId,
+}
+
+/// ``assets/profile_index.json``.
+#[derive(Serialize, Deserialize)]
+struct ProfileIndex {
+    version: u32,
+    profiles: Vec<IndexEntry>,
+}
+
+impl ProfileIndex {
+    const VERSION: u32 = 2;
+}
+
+/// The index as it was before profiles were keyed by PUUID: a list of ``Name#TAG#REGION``
+/// strings, each also naming its profile file.
+#[derive(Deserialize)]
+struct LegacyIndex {
+    profiles: Vec<String>,
+}
+
 #[allow(clippy::allow_attributes, clippy::missing_errors_doc)]
 impl PlayerLoadCtx {
-    pub fn load_player(&mut self) -> Result<Player, AppError> {
-        let api_key = Self::get_api_key();
-        let raw_username = format!("{}#{:?}", self.username, self.region);
-        let profile_path: PathBuf = self
-            .root_dir
-            .join(format!("assets/profiles/{raw_username}.json"));
-
-        let mut loaded_player = Player::default();
-        if self.indexed_players.contains(&raw_username) {
-            if let Ok(p_string) = fs::read_to_string(profile_path.clone()) {
-                loaded_player.set_api(api_key);
-                loaded_player.load_indexed_player(p_string)?;
-            } else {
-                Self::remove_player_file(&self.root_dir, &raw_username)?;
-                return Err(AppError::new("Player file could not be found"));
+    /// Loads the player described by this context: refreshes [`PlayerLoadCtx::refresh`] if set,
+    /// otherwise reads the saved profile or builds a new one from the API. Either way the result
+    /// is written back to disk, under the player's current Riot ID.
+    pub async fn load_player(&mut self) -> Result<Player, AppError> {
+        if self.api_key.is_empty() && Self::uses_live_api() {
+            return Err(AppError::new(
+                "No Riot API key set. Add one under File > Settings.",
+            ));
+        }
+
+        let player = if let Some(mut player) = self.refresh.take() {
+            // Profiles opened from the side panel, or imported, come with a bare interface;
+            // give them the cache and whatever the environment points requests at.
+            player.interface = self.interface().with_server(player.ident.known_server()?);
+            player.refresh_riot_id_async().await?;
+            if let Err(e) = player.load_new_games_async().await {
+                // Keep the matches worth retrying for next time, even though none came back.
+                Self::save_profile(&self.root_dir, &player)?;
+                return Err(e.into());
             }
+            player
         } else {
-            loaded_player = Player::new(raw_username.as_str(), api_key)?;
-            Self::update_index_file(&self.root_dir, raw_username.clone())?;
-            File::create(profile_path.clone())?;
-            loaded_player.load_new_player()?;
+            self.find_player().await?
+        };
+        Self::save_profile(&self.root_dir, &player)?;
+        Ok(player)
+    }
+
+    /// The saved profile the searched Riot ID belongs to, or a brand new one.
+    async fn find_player(&self) -> Result<Player, AppError> {
+        // Riot IDs are case-insensitive, so whatever case was typed finds the saved profile.
+        let indexed = self
+            .indexed_players
+            .iter()
+            .find(|entry| entry.riot_id.same_player(&self.riot_id));
+        // A profile saved under a region that no longer exists is passed over here; the lookup
+        // below finds where the player is now and carries it on from there.
+        if let Some(entry) = indexed
+            && let mut saved = self.load_saved(&entry.puuid)?
+            && saved.ident.unknown_server.is_none()
+        {
+            saved.refresh_riot_id_async().await?;
+            if saved.ident.riot_id().same_player(&self.riot_id) {
+                saved.try_record_rank_async().await;
+                return Ok(saved);
+            }
+            // They've renamed since, and the name may well be someone else's now. Keep the
+            // profile under its new name and look the searched name up afresh.
+            Self::save_profile(&self.root_dir, &saved)?;
+        }
+
+        let mut player = Player::with_interface_async(&self.riot_id, self.interface()).await?;
+        let tracked = self
+            .indexed_players
+            .iter()
+            .any(|entry| entry.puuid == player.start_data.puuid);
+        if tracked {
+            // Someone already tracked under an older Riot ID or region: carry on with their profile.
+            let mut saved = self.load_saved(&player.start_data.puuid)?;
+            saved.interface = player.interface;
+            saved.ident = player.ident;
+            saved.try_record_rank_async().await;
+            return Ok(saved);
         }
 
-        let mut player_file = OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .open(profile_path.clone())?;
-        player_file.write_all(serde_json::to_string(&loaded_player)?.as_bytes())?;
-        Ok(loaded_player)
+        player.queues = self.queues.clone();
+        player.checkpoints = self.checkpoints.clone();
+        player.load_new_player_async().await?;
+        Ok(player)
     }
 
-    fn get_api_key() -> String {
-        dotenv().ok();
-        std::env::var("API_TOKEN").expect("Could not find environment variables")
+    /// Reads the profile saved for `puuid`, dropping it from the index if its file is gone.
+    fn load_saved(&self, puuid: &str) -> Result<Player, AppError> {
+        let Ok(p_string) = fs::read_to_string(Self::profile_path(&self.root_dir, puuid)) else {
+            Self::remove_player_file(&self.root_dir, puuid)?;
+            return Err(AppError::new("Player file could not be found"));
+        };
+        let mut player = Player {
+            interface: self.interface(),
+            ..Default::default()
+        };
+        player.load_indexed_player(p_string)?;
+        Ok(player)
     }
 
-    pub fn read_indexed_players(root: &Path) -> Result<Vec<String>, Error> {
-        let indexed_profile_path = root.join("assets/profile_index.json");
-        #[derive(Serialize, Deserialize, Debug)]
-        struct Temp {
-            profiles: Vec<String>,
+    pub fn profile_path(root: &Path, puuid: &str) -> PathBuf {
+        root.join(format!("assets/profiles/{puuid}.json"))
+    }
+
+    /// Interface the loader talks through. Natively, matches and timelines are cached under
+    /// ``assets/cache/`` so profiles sharing games only download them once.
+    fn interface(&self) -> Interface {
+        let inter = Self::base_interface(self.api_key.clone());
+        // There's no disk to cache to in the browser.
+        #[cfg(target_arch = "wasm32")]
+        return inter;
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let cache = ResponseCache::new(self.root_dir.join("assets/cache")).compressed(true);
+            inter.with_cache(Arc::new(cache))
         }
-        let mut buf = String::new();
-        OpenOptions::new()
-            .read(true)
-            .open(indexed_profile_path)?
-            .read_to_string(&mut buf)?;
-        let indexes: Temp = match serde_json::from_str::<Temp>(&buf) {
-            Ok(n) => n,
-            Err(_) => Temp {
-                profiles: Vec::new(),
-            },
-        };
-        Ok(indexes.profiles)
     }
 
-    fn update_index_file(root: &Path, player: String) -> Result<(), Error> {
-        let indexed_profile_path = root.join("assets/profile_index.json");
-        #[derive(Serialize, Deserialize)]
-        struct Temp {
-            profiles: Vec<String>,
+    /// Where requests go. Pointing ``JADE_FIXTURES`` at a directory of recorded responses
+    /// replays those, and ``JADE_API_BASE`` sends everything to a local server such as
+    /// ``mock-riot``; otherwise it's Riot.
+    pub fn base_interface(api_key: ApiKey) -> Interface {
+        let inter = Interface::default().with_api_key(api_key);
+        if let Ok(dir) = std::env::var("JADE_FIXTURES") {
+            return inter.with_transport(Arc::new(FixtureTransport::new(dir)));
         }
+        match std::env::var("JADE_API_BASE") {
+            Ok(base_url) => inter.with_base_url(&base_url),
+            Err(_) => inter,
+        }
+    }
 
-        let mut indexed = Self::read_indexed_players(root)?;
-        indexed.push(player);
-        let serialized = serde_json::to_string(&Temp { profiles: indexed })?;
-        let mut file = OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .open(indexed_profile_path)?;
-        file.write_all(serialized.as_bytes())?;
-        Ok(())
+    /// Whether requests are going to Riot, and so need a real key.
+    pub fn uses_live_api() -> bool {
+        std::env::var("JADE_FIXTURES").is_err() && std::env::var("JADE_API_BASE").is_err()
+    }
+
+    /// Every saved profile. An index from before profiles were keyed by PUUID is migrated the
+    /// first time it's read.
+    pub fn read_indexed_players(root: &Path) -> Result<Vec<IndexEntry>, Error> {
+        let buf = fs::read_to_string(root.join("assets/profile_index.json"))?;
+        if let Ok(index) = serde_json::from_str::<ProfileIndex>(&buf) {
+            return Ok(index.profiles);
+        }
+        match serde_json::from_str::<LegacyIndex>(&buf) {
+            Ok(legacy) => Self::migrate_index(root, legacy),
+            Err(_) => Ok(Vec::new()),
+        }
+    }
+
+    /// Moves every profile in a legacy index to a file named for its PUUID. Profiles of the
+    /// same account saved under different Riot IDs are merged, and entries whose file is
+    /// missing or unreadable are dropped.
+    fn migrate_index(root: &Path, legacy: LegacyIndex) -> Result<Vec<IndexEntry>, Error> {
+        let mut migrated: Vec<(IndexEntry, Player)> = Vec::new();
+        let mut old_paths = Vec::new();
+        for key in legacy.profiles {
+            let old_path = root.join(format!("assets/profiles/{key}.json"));
+            let Some(player) = fs::read_to_string(&old_path)
+                .ok()
+                .and_then(|s| serde_json::from_str::<Player>(&s).ok())
+                .filter(|p| !p.start_data.puuid.is_empty())
+            else {
+                continue;
+            };
+            match migrated
+                .iter_mut()
+                .find(|(entry, _)| entry.puuid == player.start_data.puuid)
+            {
+                Some((_, existing)) => existing.merge(player),
+                None => migrated.push((
+                    IndexEntry {
+                        puuid: player.start_data.puuid.clone(),
+                        riot_id: player.ident.riot_id(),
+                    },
+                    player,
+                )),
+            }
+            old_paths.push(old_path);
+        }
+
+        for (entry, player) in &migrated {
+            fs::write(
+                Self::profile_path(root, &entry.puuid),
+                serde_json::to_string(player)?,
+            )?;
+        }
+        let entries: Vec<IndexEntry> = migrated.into_iter().map(|(entry, _)| entry).collect();
+        Self::write_index(root, entries.clone())?;
+        // Only once everything is safely under its new name.
+        for path in old_paths {
+            fs::remove_file(path)?;
+        }
+        Ok(entries)
     }
 
-    pub fn remove_player_file(root: &Path, player: &str) -> Result<(), Error> {
-        let indexed_profile_path = root.join("assets/profile_index.json");
-        #[derive(Serialize, Deserialize)]
-        struct Temp {
-            profiles: Vec<String>,
+    /// Writes `player` to its profile file and lists it in the index under its current Riot ID.
+    pub fn save_profile(root: &Path, player: &Player) -> Result<(), Error> {
+        let puuid = &player.start_data.puuid;
+        let path = Self::profile_path(root, puuid);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
         }
+        fs::write(path, serde_json::to_string(player)?)?;
 
+        let entry = IndexEntry {
+            puuid: puuid.clone(),
+            riot_id: player.ident.riot_id(),
+        };
         let mut indexed = Self::read_indexed_players(root)?;
-        if let Some(index) = indexed.iter().position(|x| x == &String::from(player)) {
-            indexed.remove(index);
+        match indexed.iter_mut().find(|e| &e.puuid == puuid) {
+            Some(existing) => *existing = entry,
+            None => indexed.push(entry),
         }
+        Self::write_index(root, indexed)
+    }
 
-        let serialized = serde_json::to_string(&Temp { profiles: indexed })?;
-        let mut file = OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .open(indexed_profile_path)?;
-        file.write_all(serialized.as_bytes())?;
-        Ok(())
+    pub fn remove_player_file(root: &Path, puuid: &str) -> Result<(), Error> {
+        let mut indexed = Self::read_indexed_players(root)?;
+        indexed.retain(|entry| entry.puuid != puuid);
+        Self::write_index(root, indexed)
+    }
+
+    fn write_index(root: &Path, profiles: Vec<IndexEntry>) -> Result<(), Error> {
+        let index = ProfileIndex {
+            version: ProfileIndex::VERSION,
+            profiles,
+        };
+        fs::write(
+            root.join("assets/profile_index.json"),
+            serde_json::to_string(&index)?,
+        )
     }
 }
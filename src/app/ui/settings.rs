@@ -0,0 +1,57 @@
+use crate::app::key_store::KeySource;
+use crate::ui::App;
+use egui::{Color32, Context, RichText, TextEdit, Window};
+
+use analyzer_core::interface::api_key::KeyStatus;
+
+impl App {
+    /// Window for entering and checking the Riot API key. A new key takes effect immediately,
+    /// including for the player already on screen.
+    pub fn display_settings(&mut self, ctx: &Context) {
+        let mut open = self.show_settings;
+        Window::new("Settings")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label("Riot API key");
+                ui.add(
+                    TextEdit::singleline(&mut self.key_input)
+                        .password(true)
+                        .hint_text("RGAPI-..."),
+                );
+                ui.horizontal(|ui| {
+                    if ui.button("Save").clicked() {
+                        if let Err(e) = self.keys.set(&self.key_input) {
+                            self.err = Some(e);
+                        }
+                        self.key_input.clear();
+                    }
+                    if ui.button("Check again").clicked() {
+                        self.keys.validate();
+                    }
+                });
+
+                let source = match self.keys.source() {
+                    KeySource::Config => "Loaded from assets/config.json",
+                    KeySource::Environment => "Loaded from API_TOKEN",
+                    KeySource::Entered => "Entered this session",
+                    KeySource::None => "No key found",
+                };
+                ui.label(RichText::new(source).small());
+                self.key_status_label(ui);
+            });
+        self.show_settings = open;
+    }
+
+    /// One line on how the last key check went, coloured by how worried to be.
+    pub fn key_status_label(&self, ui: &mut egui::Ui) {
+        let (text, color) = match self.keys.status() {
+            None => (String::from("Checking API key..."), Color32::GRAY),
+            Some(Ok(KeyStatus::Valid)) => (KeyStatus::Valid.to_string(), Color32::GREEN),
+            Some(Ok(status)) => (status.to_string(), Color32::RED),
+            Some(Err(e)) => (format!("Could not check API key: {e}"), Color32::YELLOW),
+        };
+        ui.label(RichText::new(text).small().color(color));
+    }
+}
@@ -15,10 +15,12 @@ impl App {
         graph_dimensions: (usize, usize), //could have enum that describes graph type, and 2d array
                                   //that describes location of each graph in column/row
     ) {
+        #[derive(Clone)]
         struct G {
             line: Vec<PlotPoint>,
             _type: GraphType,
         }
+        let checkpoint = self.checkpoint;
         /* Closure for creating plot; for scope reasons it needs to be a closure */
         let create_plot = |ui: &mut Ui, graph: GraphType, plots: Vec<PlotPoint>| {
             let plot_name = format!("{graph}");
@@ -69,43 +71,36 @@ impl App {
                 });
         };
 
-        /* Built a strip of graphs. Revisit after implementing data collection */
+        /* Built a strip of graphs, filled row by row in this order */
+        let types = [
+            GraphType::GD(checkpoint),
+            GraphType::XPD(checkpoint),
+            GraphType::CSD(checkpoint),
+            GraphType::LevelDiff(checkpoint),
+            GraphType::CSM,
+            GraphType::DPM,
+            GraphType::KP,
+            GraphType::LP,
+        ];
+        let graphs: Vec<G> = plots
+            .iter()
+            .zip(types)
+            .map(|(line, _type)| G {
+                line: line.clone(),
+                _type,
+            })
+            .collect();
         StripBuilder::new(ui)
             .sizes(
                 Size::relative((graph_dimensions.0 as f32).recip()),
                 graph_dimensions.0,
             )
             .vertical(|mut strip| {
-                strip.cell(|ui| {
-                    make_row(
-                        ui,
-                        vec![
-                            G {
-                                line: plots[0].clone(),
-                                _type: GraphType::GD15,
-                            },
-                            G {
-                                line: plots[1].clone(),
-                                _type: GraphType::CSM,
-                            },
-                        ],
-                    );
-                });
-                strip.cell(|ui| {
-                    make_row(
-                        ui,
-                        vec![
-                            G {
-                                line: plots[2].clone(),
-                                _type: GraphType::DPM,
-                            },
-                            G {
-                                line: plots[3].clone(),
-                                _type: GraphType::KP,
-                            },
-                        ],
-                    );
-                });
+                for row in graphs.chunks(graph_dimensions.1) {
+                    strip.cell(|ui| {
+                        make_row(ui, row.to_vec());
+                    });
+                }
             });
     }
 }
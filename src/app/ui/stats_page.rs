@@ -1,4 +1,6 @@
-use egui::Ui;
+use analyzer_core::data_processor::items::Items;
+use analyzer_core::queue::QueueType;
+use egui::{Color32, RichText, Ui};
 use egui_plot::PlotPoint;
 
 use crate::ui::App;
@@ -9,49 +11,71 @@ impl App {
          * Generate a graph for each one (edit how drawing the strip works)
          * draw that here */
 
-        let g_gd15: Vec<PlotPoint> = self
+        let minutes = self.loaded_player.checkpoint_minutes();
+        if !minutes.contains(&self.checkpoint)
+            && let Some(minute) = minutes.first()
+        {
+            self.checkpoint = *minute;
+        }
+        // Games that ended before the checkpoint leave a gap rather than a zero.
+        let checkpoint_line = |points: Vec<Option<i32>>| -> Vec<PlotPoint> {
+            points
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, v)| v.map(|v| PlotPoint::new(i as f64, v as f64)))
+                .collect()
+        };
+        let player = &self.loaded_player;
+        let g_gd = checkpoint_line(player.gd_points(self.queue_filter, self.checkpoint));
+        let g_xpd = checkpoint_line(player.xpd_points(self.queue_filter, self.checkpoint));
+        let g_csd = checkpoint_line(player.csd_points(self.queue_filter, self.checkpoint));
+        let g_level = checkpoint_line(player.level_diff_points(self.queue_filter, self.checkpoint));
+
+        let g_csm: Vec<PlotPoint> = self
             .loaded_player
-            .gd15_points()
+            .csm_points(self.queue_filter)
             .into_iter()
             .enumerate()
             .map(|(i, v)| PlotPoint::new(i as f64, v as f64))
             .collect();
 
-        let g_csm: Vec<PlotPoint> = self
+        let g_dpm: Vec<PlotPoint> = self
             .loaded_player
-            .csm_points()
+            .dpm_points(self.queue_filter)
             .into_iter()
             .enumerate()
             .map(|(i, v)| PlotPoint::new(i as f64, v as f64))
             .collect();
 
-        let g_dpm: Vec<PlotPoint> = self
+        let g_kp: Vec<PlotPoint> = self
             .loaded_player
-            .dpm_points()
+            .kp_points(self.queue_filter)
             .into_iter()
             .enumerate()
             .map(|(i, v)| PlotPoint::new(i as f64, v as f64))
             .collect();
 
-        let g_kp: Vec<PlotPoint> = self
+        let g_lp: Vec<PlotPoint> = self
             .loaded_player
-            .kp_points()
+            .lp_points(self.ranked_queue())
             .into_iter()
             .enumerate()
-            .map(|(i, v)| PlotPoint::new(i as f64, v as f64))
+            .map(|(i, (_, v))| PlotPoint::new(i as f64, v as f64))
             .collect();
 
-        let graphs: Vec<Vec<PlotPoint>> = vec![g_gd15, g_csm, g_dpm, g_kp];
+        let graphs: Vec<Vec<PlotPoint>> =
+            vec![g_gd, g_xpd, g_csd, g_level, g_csm, g_dpm, g_kp, g_lp];
 
         ui.vertical_centered_justified(|ui| {
             //username label
             let max_width = ui.available_width();
             ui.set_max_width(200.0);
-            //call player_info_display(ui) instead
-            ui.label(format!(
-                "Current user:\n{} ({:?})",
-                self.username, self.region
-            ));
+            self.player_info_display(ui);
+            self.queue_filter_box(ui);
+            self.checkpoint_box(ui, &minutes);
+            if ui.button("Game chart").clicked() {
+                self.open_game_chart();
+            }
 
             //graph grid
             ui.set_max_width(max_width);
@@ -59,7 +83,110 @@ impl App {
         });
     }
 
+    /// Combo box picking which of the profile's queues the graphs are drawn from.
+    fn queue_filter_box(&mut self, ui: &mut Ui) {
+        let selected = match self.queue_filter {
+            Some(queue) => queue.to_string(),
+            None => String::from("All queues"),
+        };
+        egui::ComboBox::from_id_salt("Queue_Filter")
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut self.queue_filter, None, "All queues");
+                for queue in &self.loaded_player.queues {
+                    ui.selectable_value(&mut self.queue_filter, Some(*queue), queue.to_string());
+                }
+            });
+    }
+
+    /// Combo box picking which checkpoint minute the lane difference graphs show.
+    fn checkpoint_box(&mut self, ui: &mut Ui, minutes: &[u32]) {
+        egui::ComboBox::from_id_salt("Checkpoint_Box")
+            .selected_text(format!("At {} min", self.checkpoint))
+            .show_ui(ui, |ui| {
+                for minute in minutes {
+                    ui.selectable_value(&mut self.checkpoint, *minute, format!("{minute} min"));
+                }
+            });
+    }
+
+    /// Ladder the rank info and LP graph are shown for: flex when that's the selected filter,
+    /// solo otherwise.
+    fn ranked_queue(&self) -> QueueType {
+        match self.queue_filter {
+            Some(QueueType::RankedFlex) => QueueType::RankedFlex,
+            _ => QueueType::RankedSolo,
+        }
+    }
+
     pub fn player_info_display(&mut self, ui: &mut Ui) {
-        todo!()
+        ui.label(format!(
+            "Current user:\n{} ({})",
+            self.username, self.region
+        ));
+        let queue = self.ranked_queue();
+        match self.loaded_player.current_rank(queue) {
+            Some(rank) => {
+                ui.label(format!("{queue}: {}", rank.rank_string()));
+                ui.label(format!(
+                    "{}W {}L ({:.0}%)",
+                    rank.wins,
+                    rank.losses,
+                    rank.win_rate()
+                ));
+                if rank.hot_streak {
+                    ui.label(RichText::new("On a hot streak").color(Color32::ORANGE));
+                }
+                if rank.inactive {
+                    ui.label(RichText::new("Inactive").color(Color32::GRAY));
+                }
+            }
+            None => {
+                ui.label(format!("{queue}: Unranked"));
+            }
+        }
+        if let Some(code) = &self.loaded_player.ident.unknown_server {
+            ui.label(
+                RichText::new(format!("Saved for region {code}, which no longer exists"))
+                    .small()
+                    .color(Color32::RED),
+            )
+            .on_hover_text("Search for the player under their current region to refresh.");
+        }
+        if let Some(error) = &self.loaded_player.rank_error {
+            ui.label(
+                RichText::new("Rank couldn't be updated")
+                    .small()
+                    .color(Color32::YELLOW),
+            )
+            .on_hover_text(error);
+        }
+        if let Err(e) = Items::shared().status() {
+            ui.label(
+                RichText::new("Item values may be off")
+                    .small()
+                    .color(Color32::YELLOW),
+            )
+            .on_hover_text(e.to_string());
+        }
+        let drift = self.loaded_player.interface.schema_drift();
+        if !drift.is_empty() {
+            ui.label(
+                RichText::new("Riot's match data has changed shape; the models may need updating")
+                    .small()
+                    .color(Color32::YELLOW),
+            )
+            .on_hover_text(drift.to_string());
+        }
+        let pending = self.loaded_player.pending_ids.len();
+        if pending > 0 {
+            ui.label(
+                RichText::new(format!(
+                    "{pending} game(s) couldn't be fetched; they'll be retried on the next reload"
+                ))
+                .small()
+                .color(Color32::YELLOW),
+            );
+        }
     }
 }